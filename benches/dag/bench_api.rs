@@ -170,3 +170,317 @@ pub fn connections_benchmark(c: &mut Criterion) {
         b.iter(|| black_box(dag.connections(String::from("9"))))
     });
 }
+
+pub fn topological_sort_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::TopologicalSort", |b| {
+        b.iter(|| black_box(dag.topological_sort()))
+    });
+
+    c.bench_function("dag::api::TopologicalSort (lazy, first vertex)", |b| {
+        b.iter(|| black_box(dag.topological_iter().next()))
+    });
+}
+
+pub fn generations_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::Generations", |b| {
+        b.iter(|| black_box(dag.generations()))
+    });
+}
+
+pub fn parents_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::Parents (vertex does not exist)", |b| {
+        b.iter(|| black_box(dag.parents(String::from("10"))))
+    });
+
+    c.bench_function("dag::api::Parents (vertex exists)", |b| {
+        b.iter(|| black_box(dag.parents(String::from("9"))))
+    });
+}
+
+pub fn roots_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::Roots", |b| b.iter(|| black_box(dag.roots())));
+}
+
+pub fn leaves_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::Leaves", |b| b.iter(|| black_box(dag.leaves())));
+}
+
+pub fn ancestors_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::Ancestors", |b| {
+        b.iter(|| black_box(dag.ancestors(String::from("9"))))
+    });
+}
+
+pub fn descendants_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::Descendants", |b| {
+        b.iter(|| black_box(dag.descendants(String::from("0"))))
+    });
+}
+
+pub fn reachable_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::Reachable (path does not exist)", |b| {
+        b.iter(|| black_box(dag.has_path(String::from("9"), String::from("0"))))
+    });
+
+    c.bench_function("dag::api::Reachable (path exists)", |b| {
+        b.iter(|| black_box(dag.has_path(String::from("0"), String::from("9"))))
+    });
+}
+
+/// Builds a dag of n vertices where every vertex i has an edge to i + 1, i + 2,
+/// and i + 10 (where those exist). There are exponentially many paths from the
+/// first vertex to the last.
+fn setup_large(n: usize) -> Result<BTreeDAG<usize>, Error> {
+    let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+    for i in 0..n {
+        dag.add_vertex(i);
+    }
+    for i in 0..n {
+        for step in &[1, 2, 10] {
+            if i + step < n {
+                dag.add_edge(i, i + step)?;
+            }
+        }
+    }
+    Ok(dag)
+}
+
+pub fn add_edge_large_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dag::api::AddEdge (large)");
+    group.sample_size(20);
+    for n in &[10_000, 50_000] {
+        let mut dag = setup_large(*n).unwrap();
+        // The cycle check searches from 1 for 0, which it cannot reach, so
+        // every other vertex is visited before the existing edge is accepted.
+        group.bench_function(format!("{} vertices (edge accepted)", n), |b| {
+            b.iter(|| black_box(dag.add_edge(0, 1)))
+        });
+        // The cycle check searches from 0 for the last vertex, which every
+        // vertex reaches, and rejects the edge once it is found.
+        group.bench_function(format!("{} vertices (cycle rejected)", n), |b| {
+            b.iter(|| black_box(dag.add_edge(n - 1, 0)))
+        });
+    }
+    group.finish();
+}
+
+pub fn incremental_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dag::incremental::IncrementalBTreeDAG (large)");
+    group.sample_size(20);
+    for n in &[10_000, 50_000] {
+        let mut dag: IncrementalBTreeDAG<usize> = setup_large(*n).unwrap().into();
+        // The edge agrees with the maintained order, so nothing is searched.
+        group.bench_function(format!("{} vertices AddEdge (edge accepted)", n), |b| {
+            b.iter(|| black_box(dag.add_edge(0, 1)))
+        });
+        // The search is confined to the vertices between 9 and 0 in the order.
+        group.bench_function(format!("{} vertices AddEdge (cycle rejected)", n), |b| {
+            b.iter(|| black_box(dag.add_edge(9, 0)))
+        });
+        group.bench_function(format!("{} vertices topological_order", n), |b| {
+            b.iter(|| black_box(dag.topological_order()))
+        });
+    }
+    group.finish();
+}
+
+pub fn would_create_cycle_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    let first = String::from("0");
+    let last = String::from("9");
+    c.bench_function("dag::api::WouldCreateCycle (cycle)", |b| {
+        b.iter(|| black_box(dag.would_create_cycle(&last, &first)))
+    });
+
+    c.bench_function("dag::api::WouldCreateCycle (no cycle)", |b| {
+        b.iter(|| black_box(dag.would_create_cycle(&first, &last)))
+    });
+}
+
+pub fn transitive_reduction_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::TransitiveReduction (redundant edges)", |b| {
+        b.iter(|| black_box(dag.redundant_edges()))
+    });
+
+    c.bench_function(
+        "dag::api::TransitiveReduction (transitive reduction)",
+        |b| b.iter(|| black_box(dag.transitive_reduction())),
+    );
+}
+
+pub fn transitive_closure_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::TransitiveClosure", |b| {
+        b.iter(|| black_box(dag.transitive_closure()))
+    });
+}
+
+pub fn reachability_index_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dag::reachability::ReachabilityIndex (large)");
+    group.sample_size(20);
+    let n = 10_000;
+    let dag = setup_large(n).unwrap();
+    let bitset = ReachabilityIndex::bitset(&dag);
+    let intervals = ReachabilityIndex::intervals(&dag);
+    group.bench_function(format!("{} vertices bitset reaches", n), |b| {
+        b.iter(|| black_box(bitset.reaches(&0, &(n - 1))))
+    });
+    group.bench_function(format!("{} vertices intervals reaches", n), |b| {
+        b.iter(|| black_box(intervals.reaches(&0, &(n - 1))))
+    });
+    group.bench_function(format!("{} vertices intervals build", n), |b| {
+        b.iter(|| black_box(ReachabilityIndex::intervals(&dag)))
+    });
+    group.finish();
+}
+
+pub fn critical_path_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::CriticalPath", |b| {
+        b.iter(|| black_box(dag.critical_path(|v| v.len())))
+    });
+}
+
+pub fn schedule_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::ScheduleAnalysis", |b| {
+        b.iter(|| black_box(dag.schedule(|v| v.len())))
+    });
+}
+
+pub fn shortest_path_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::ShortestPath (path exists)", |b| {
+        b.iter(|| black_box(dag.shortest_path(String::from("0"), String::from("9"))))
+    });
+
+    c.bench_function("dag::api::ShortestPath (path does not exist)", |b| {
+        b.iter(|| black_box(dag.shortest_path(String::from("9"), String::from("0"))))
+    });
+}
+
+pub fn all_paths_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::AllPaths (count paths)", |b| {
+        b.iter(|| black_box(dag.count_paths(String::from("0"), String::from("9"))))
+    });
+
+    c.bench_function("dag::api::AllPaths (enumerate paths)", |b| {
+        b.iter(|| {
+            black_box(
+                dag.paths(String::from("0"), String::from("9"))
+                    .unwrap()
+                    .count(),
+            )
+        })
+    });
+}
+
+pub fn common_ancestors_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::CommonAncestors (lowest common ancestors)", |b| {
+        b.iter(|| black_box(dag.lowest_common_ancestors(String::from("8"), String::from("9"))))
+    });
+}
+
+pub fn dominators_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::Dominators (dominator tree)", |b| {
+        b.iter(|| black_box(dag.dominator_tree(String::from("0"))))
+    });
+
+    c.bench_function("dag::api::Dominators (post-dominator tree)", |b| {
+        b.iter(|| black_box(dag.post_dominator_tree(String::from("9"))))
+    });
+}
+
+pub fn impact_analysis_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::ImpactAnalysis", |b| {
+        b.iter(|| black_box(dag.affected(vec![String::from("5")])))
+    });
+}
+
+pub fn prune_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    // Every vertex is a descendant of 0, and the dag is cloned on every
+    // iteration so there is something left to prune.
+    c.bench_function("dag::api::Prune (cascade)", |b| {
+        b.iter(|| black_box(dag.clone().prune(String::from("0"))))
+    });
+
+    // Every descendant of 1 is also a child of 0, so only 1 is removed.
+    c.bench_function("dag::api::Prune (orphans)", |b| {
+        b.iter(|| {
+            black_box(
+                dag.clone()
+                    .prune_with(String::from("1"), PruneMode::Orphans),
+            )
+        })
+    });
+}
+
+pub fn preview_removal_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    c.bench_function("dag::api::PreviewRemoval (remove vertex)", |b| {
+        b.iter(|| black_box(dag.remove_vertex_preview(String::from("5"))))
+    });
+
+    c.bench_function("dag::api::PreviewRemoval (prune)", |b| {
+        b.iter(|| black_box(dag.prune_preview(String::from("0"), PruneMode::Cascade)))
+    });
+}
+
+pub fn remove_vertex_bypass_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    // 5 has five parents, and four children, so twenty bypass edges are
+    // considered. The dag is cloned on every iteration.
+    c.bench_function("dag::api::RemoveVertexBypass", |b| {
+        b.iter(|| black_box(dag.clone().remove_vertex_bypass(String::from("5"))))
+    });
+}
+
+pub fn split_edge_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    // The dag is cloned on every iteration, so the new vertex does not exist.
+    c.bench_function("dag::api::SplitEdge", |b| {
+        b.iter(|| {
+            black_box(dag.clone().split_edge(
+                String::from("4"),
+                String::from("5"),
+                String::from("10"),
+            ))
+        })
+    });
+}
+
+pub fn merge_vertices_benchmark(c: &mut Criterion) {
+    let dag = setup().unwrap();
+    // Every vertex reaches every later one, so only neighbouring vertices
+    // can be merged. The dag is cloned on every iteration.
+    c.bench_function("dag::api::MergeVertices (merged)", |b| {
+        b.iter(|| {
+            black_box(dag.clone().merge_vertices(
+                String::from("4"),
+                String::from("5"),
+                String::from("4"),
+            ))
+        })
+    });
+
+    let mut dag = dag;
+    c.bench_function("dag::api::MergeVertices (cycle rejected)", |b| {
+        b.iter(|| {
+            black_box(dag.merge_vertices(String::from("0"), String::from("9"), String::from("0")))
+        })
+    });
+}
@@ -13,5 +13,31 @@ criterion_group!(
     remove_vertex_benchmark,
     remove_edge_benchmark,
     adjacent_benchmark,
-    connections_benchmark
+    connections_benchmark,
+    topological_sort_benchmark,
+    generations_benchmark,
+    parents_benchmark,
+    roots_benchmark,
+    leaves_benchmark,
+    ancestors_benchmark,
+    descendants_benchmark,
+    reachable_benchmark,
+    add_edge_large_benchmark,
+    incremental_benchmark,
+    would_create_cycle_benchmark,
+    transitive_reduction_benchmark,
+    transitive_closure_benchmark,
+    reachability_index_benchmark,
+    critical_path_benchmark,
+    schedule_benchmark,
+    shortest_path_benchmark,
+    all_paths_benchmark,
+    common_ancestors_benchmark,
+    dominators_benchmark,
+    impact_analysis_benchmark,
+    prune_benchmark,
+    preview_removal_benchmark,
+    remove_vertex_bypass_benchmark,
+    split_edge_benchmark,
+    merge_vertices_benchmark
 );
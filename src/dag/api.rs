@@ -1,4 +1,11 @@
 use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::ops::{Add, Sub};
+
+use crate::dag::{
+    BTreeDAG, DominatorTree, PathsIter, Removal, Schedule, TopologicalIter, Traversal,
+    TraversalIter,
+};
 
 /// `Vertices` returns the set of the vertices which comprise the dag.
 ///
@@ -37,6 +44,8 @@ where
 }
 
 /// `AddEdge` add an edge from the vertex x to the vertex y, if it is not there.
+/// An edge which would close a cycle is rejected with `EdgeError::Cycle`, carrying
+/// the existing path from y to x.
 ///
 /// # Example
 ///
@@ -44,16 +53,26 @@ where
 /// extern crate alloc;
 /// use alloc::collections::btree_set::BTreeSet;
 /// use btree_dag::{BTreeDAG, AddVertex, AddEdge, GetVertexValue};
-/// use btree_dag::Error;
+/// use btree_dag::EdgeError;
 /// let mut dag: BTreeDAG<String> = BTreeDAG::new();
 /// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("waypoint"));
 /// dag.add_vertex(String::from("destination"));
-/// dag.add_edge(String::from("origin"), String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("waypoint"));
+/// dag.add_edge(String::from("waypoint"), String::from("destination"));
 ///
 /// let x_value: &BTreeSet<String> = dag.get_vertex_value(String::from("origin")).unwrap();
-/// assert!(x_value.contains(&String::from("destination")));
+/// assert!(x_value.contains(&String::from("waypoint")));
 ///
 /// assert!(dag.get_vertex_value(String::from("destination")).unwrap().is_empty());
+///
+/// let err = dag.add_edge(String::from("destination"), String::from("origin")).unwrap_err();
+/// let path: Vec<String> = vec![
+///     String::from("origin"),
+///     String::from("waypoint"),
+///     String::from("destination"),
+/// ];
+/// assert_eq!(err, EdgeError::Cycle(path));
 /// ```
 pub trait AddEdge<T> {
     type Error;
@@ -135,6 +154,98 @@ where
     fn remove_vertex(&mut self, x: T) -> Result<BTreeSet<T>, Self::Error>;
 }
 
+/// `SplitEdge` replaces the edge from x to y with the edges from x to z, and from z
+/// to y, where z is a new vertex. Since z is new, no cycle can be formed, and either
+/// the whole edge is split or, on error, the dag is left untouched. An error is thrown
+/// if x, or y do not exist, if there is no edge from x to y, or if z already exists.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Adjacent, SplitEdge, Error};
+/// let mut dag: BTreeDAG<&str> = BTreeDAG::new();
+/// dag.add_vertex("build");
+/// dag.add_vertex("deploy");
+/// dag.add_edge("build", "deploy");
+///
+/// dag.split_edge("build", "deploy", "test").unwrap();
+/// assert!(dag.adjacent("build", "test").unwrap());
+/// assert!(dag.adjacent("test", "deploy").unwrap());
+/// assert!(!dag.adjacent("build", "deploy").unwrap());
+///
+/// assert_eq!(dag.split_edge("build", "test", "deploy"), Err(Error::VertexExists));
+/// ```
+pub trait SplitEdge<T> {
+    type Error;
+    fn split_edge(&mut self, x: T, y: T, z: T) -> Result<(), Self::Error>;
+}
+
+/// `MergeVertices` merges the vertices a, and b into the vertex named into, which is
+/// either a, b, or a new vertex. Every edge into, or out of a, or b is redirected to
+/// the merged vertex, and an edge between a, and b is dropped. If there is any other
+/// path from a to b, or from b to a, the merge would create a cycle, and it is
+/// rejected with that path as an `EdgeError::Cycle`. An error is also thrown if a, or
+/// b do not exist, or if into is some other existing vertex. On error, the dag is left
+/// untouched.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Adjacent, MergeVertices, EdgeError, Vertices};
+/// let mut dag: BTreeDAG<&str> = BTreeDAG::new();
+/// dag.add_vertex("source");
+/// dag.add_vertex("artifact_A");
+/// dag.add_vertex("artifact_B");
+/// dag.add_vertex("package");
+/// dag.add_edge("source", "artifact_A");
+/// dag.add_edge("artifact_B", "package");
+///
+/// dag.merge_vertices("artifact_A", "artifact_B", "artifact").unwrap();
+/// assert_eq!(dag.vertices().len(), 3);
+/// assert!(dag.adjacent("source", "artifact").unwrap());
+/// assert!(dag.adjacent("artifact", "package").unwrap());
+///
+/// // Merging source, and package would turn the path between them into a cycle.
+/// assert_eq!(
+///     dag.merge_vertices("source", "package", "source"),
+///     Err(EdgeError::Cycle(vec!["source", "artifact", "package"]))
+/// );
+/// ```
+pub trait MergeVertices<T> {
+    type Error;
+    fn merge_vertices(&mut self, a: T, b: T, into: T) -> Result<(), Self::Error>;
+}
+
+/// `RemoveVertexBypass` removes the vertex x, and adds an edge from each of x's
+/// parents to each of x's children, so every vertex which reached another through x
+/// still does. Such edges can never close a cycle. Returns the edges which were
+/// added, leaving out those which were already there. An error is thrown if x does
+/// not exist.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, RemoveVertexBypass, Reachable, Vertices};
+/// let mut dag: BTreeDAG<&str> = BTreeDAG::new();
+/// dag.add_vertex("extract");
+/// dag.add_vertex("transform");
+/// dag.add_vertex("load");
+/// dag.add_edge("extract", "transform");
+/// dag.add_edge("transform", "load");
+///
+/// let added = dag.remove_vertex_bypass("transform").unwrap();
+/// assert!(added.contains(&("extract", "load")));
+/// assert_eq!(dag.vertices().len(), 2);
+/// assert!(dag.has_path("extract", "load").unwrap());
+/// ```
+pub trait RemoveVertexBypass<T>
+where
+    T: Ord,
+{
+    type Error;
+    fn remove_vertex_bypass(&mut self, x: T) -> Result<BTreeSet<(T, T)>, Self::Error>;
+}
+
 /// `Adjacent` tests whether there is an edge from the vertex x to the vertex y.
 /// An error is thrown if either x, or y do not exist. By definition of adjacent there
 /// must exist an edge e, with value (x, y) in order for vertices x, and y to be
@@ -144,7 +255,7 @@ where
 ///
 /// ```
 /// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Adjacent};
-/// use btree_dag::Error;
+/// use btree_dag::EdgeError;
 /// let mut dag: BTreeDAG<String> = BTreeDAG::new();
 /// dag.add_vertex(String::from("origin"));
 /// dag.add_vertex(String::from("destination"));
@@ -154,8 +265,8 @@ where
 /// // Note: the dag is directed, and the definition of adjacent
 /// // can be phrased, if there exists a relationship from x to y. Therefore
 /// // A and B adjacent implies B and A cannot be adjacent.
-/// let err: Error = dag.add_edge(String::from("destination"), String::from("origin")).unwrap_err();
-/// assert_eq!(err, Error::EdgeExists)
+/// let err: EdgeError<String> = dag.add_edge(String::from("destination"), String::from("origin")).unwrap_err();
+/// assert_eq!(err, EdgeError::Cycle(vec![String::from("origin"), String::from("destination")]))
 /// ```
 pub trait Adjacent<T> {
     type Error;
@@ -180,14 +291,29 @@ pub trait Connections<T> {
     fn connections(&self, x: T) -> Option<&BTreeSet<T>>;
 }
 
+/// `PruneMode` selects which descendants of the pruned vertex `Prune::prune_with`
+/// removes along with it.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PruneMode {
+    /// Remove every descendant, even those which other surviving vertices still
+    /// point at.
+    Cascade,
+    /// Remove only the descendants left without any parents, i.e. those which
+    /// are reachable from the remaining vertices only through the pruned vertex.
+    Orphans,
+}
+
 /// `Prune` remove vertex x and recursively remove all children
-/// of x. Prune will error if x does not exists, and by `BTreeDAG`'s
-/// contract, all recursive calls cannot error.
+/// of x. Prune will error if x does not exists, in which case nothing is
+/// removed. Vertices reachable by more than one path (e.g. the bottom of a
+/// diamond) are removed once. `prune_with` selects between removing every
+/// descendant and removing only the orphaned ones. Both return the set of
+/// removed vertices.
 ///
 /// # Example
 ///
 /// ```
-/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Connections, Prune, Vertices, GetVertexValue};
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Connections, Prune, PruneMode, Vertices, GetVertexValue};
 /// use std::collections::BTreeSet;
 /// let mut dag: BTreeDAG<String> = BTreeDAG::new();
 /// dag.add_vertex(String::from("origin"));
@@ -198,15 +324,707 @@ pub trait Connections<T> {
 /// dag.add_edge(String::from("origin"), String::from("waypoint"));
 /// dag.add_edge(String::from("waypoint"), String::from("destination_A"));
 /// dag.add_edge(String::from("waypoint"), String::from("destination_B"));
+/// let before = dag.clone();
 ///
-/// dag.prune(String::from("waypoint"));
+/// let removed: BTreeSet<String> = dag.prune(String::from("waypoint")).unwrap();
+/// assert_eq!(removed.len(), 3);
 ///
 /// assert_eq!(dag.vertices().len(), 1);
 /// assert!(dag.vertices().contains(&String::from("origin")));
 /// let remaining_children_of_origin: BTreeSet<String> = BTreeSet::new();
 /// assert_eq!(dag.get_vertex_value(String::from("origin")).unwrap(), &remaining_children_of_origin);
+///
+/// // Only orphans are pruned, so destination_B survives through its other parent.
+/// let mut dag = before;
+/// dag.add_edge(String::from("origin"), String::from("destination_B"));
+/// let removed = dag.prune_with(String::from("waypoint"), PruneMode::Orphans).unwrap();
+/// assert_eq!(removed.len(), 2);
+/// assert!(dag.vertices().contains(&String::from("destination_B")));
 /// ```
 pub trait Prune<T> {
     type Error;
-    fn prune(&mut self, x: T) -> Result<(), Self::Error>;
-}
\ No newline at end of file
+    fn prune(&mut self, x: T) -> Result<BTreeSet<T>, Self::Error>;
+    fn prune_with(&mut self, x: T, mode: PruneMode) -> Result<BTreeSet<T>, Self::Error>;
+}
+
+/// `TopologicalSort` orders the vertices such that for every edge from the vertex x
+/// to the vertex y, x comes before y. Whenever more than one vertex may come next,
+/// the least vertex (by `T: Ord`) is chosen, so the ordering is deterministic.
+/// `topological_iter` is the lazy variant of `topological_sort`.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, TopologicalSort};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("socks"));
+/// dag.add_vertex(String::from("shoes"));
+/// dag.add_vertex(String::from("pants"));
+/// dag.add_edge(String::from("socks"), String::from("shoes"));
+/// dag.add_edge(String::from("pants"), String::from("shoes"));
+///
+/// let order: Vec<&String> = dag.topological_sort();
+/// assert_eq!(order, vec!["pants", "socks", "shoes"]);
+///
+/// let mut iter = dag.topological_iter();
+/// assert_eq!(iter.next(), Some(&String::from("pants")));
+/// ```
+pub trait TopologicalSort<T>
+where
+    T: Ord,
+{
+    fn topological_sort(&self) -> Vec<&T>;
+    fn topological_iter(&self) -> TopologicalIter<'_, T>;
+}
+
+/// `Generations` partitions the vertices into generations such that every vertex
+/// in generation k has all of its parents in generations less than k, and at least
+/// one parent in generation k - 1. The vertices of a single generation do not depend
+/// on one another, and so may be processed in parallel.
+///
+/// # Example
+///
+/// ```
+/// extern crate alloc;
+/// use alloc::collections::btree_set::BTreeSet;
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Generations};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("fetch"));
+/// dag.add_vertex(String::from("lint"));
+/// dag.add_vertex(String::from("build"));
+/// dag.add_vertex(String::from("test"));
+/// dag.add_edge(String::from("fetch"), String::from("lint"));
+/// dag.add_edge(String::from("fetch"), String::from("build"));
+/// dag.add_edge(String::from("build"), String::from("test"));
+///
+/// let generations: Vec<BTreeSet<&String>> = dag.generations();
+/// assert_eq!(generations.len(), 3);
+/// assert!(generations[0].contains(&String::from("fetch")));
+/// assert!(generations[1].contains(&String::from("lint")));
+/// assert!(generations[1].contains(&String::from("build")));
+/// assert!(generations[2].contains(&String::from("test")));
+/// ```
+pub trait Generations<T>
+where
+    T: Ord,
+{
+    fn generations(&self) -> Vec<BTreeSet<&T>>;
+}
+
+/// `Parents` lists all vertices x such that there is an edge from the vertex x to
+/// the vertex y, i.e. the reverse of `Connections`. Returns `None` if y does not exist.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Parents};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("destination"));
+///
+/// assert!(dag.parents(String::from("destination")).unwrap().contains(&String::from("origin")));
+/// assert!(dag.parents(String::from("origin")).unwrap().is_empty());
+/// ```
+pub trait Parents<T> {
+    fn parents(&self, y: T) -> Option<&BTreeSet<T>>;
+}
+
+/// `InDegree` returns the number of edges pointing at the vertex x. Returns `None`
+/// if x does not exist.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, InDegree};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("destination"));
+///
+/// assert_eq!(dag.in_degree(String::from("destination")), Some(1));
+/// assert_eq!(dag.in_degree(String::from("origin")), Some(0));
+/// ```
+pub trait InDegree<T> {
+    fn in_degree(&self, x: T) -> Option<usize>;
+}
+
+/// `OutDegree` returns the number of edges leaving the vertex x. Returns `None`
+/// if x does not exist.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, OutDegree};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("destination"));
+///
+/// assert_eq!(dag.out_degree(String::from("origin")), Some(1));
+/// assert_eq!(dag.out_degree(String::from("destination")), Some(0));
+/// ```
+pub trait OutDegree<T> {
+    fn out_degree(&self, x: T) -> Option<usize>;
+}
+
+/// `Roots` returns the set of vertices which have no incoming edges.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Roots};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("destination"));
+///
+/// assert_eq!(dag.roots().len(), 1);
+/// assert!(dag.roots().contains(&String::from("origin")));
+/// ```
+pub trait Roots<T>
+where
+    T: Ord,
+{
+    fn roots(&self) -> BTreeSet<&T>;
+}
+
+/// `Leaves` returns the set of vertices which have no outgoing edges.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Leaves};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("destination"));
+///
+/// assert_eq!(dag.leaves().len(), 1);
+/// assert!(dag.leaves().contains(&String::from("destination")));
+/// ```
+pub trait Leaves<T>
+where
+    T: Ord,
+{
+    fn leaves(&self) -> BTreeSet<&T>;
+}
+
+/// `Ancestors` returns the set of all vertices from which there is a path to the
+/// vertex x. `ancestors_iter` streams the same vertices in breadth first or depth
+/// first order. Both return `None` if x does not exist.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Ancestors, Traversal};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("waypoint"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("waypoint"));
+/// dag.add_edge(String::from("waypoint"), String::from("destination"));
+///
+/// let ancestors = dag.ancestors(String::from("destination")).unwrap();
+/// assert_eq!(ancestors.len(), 2);
+/// assert!(ancestors.contains(&String::from("origin")));
+///
+/// let mut iter = dag
+///     .ancestors_iter(String::from("destination"), Traversal::BreadthFirst)
+///     .unwrap();
+/// assert_eq!(iter.next(), Some(&String::from("waypoint")));
+/// assert_eq!(iter.next(), Some(&String::from("origin")));
+/// assert_eq!(iter.next(), None);
+/// ```
+pub trait Ancestors<T>
+where
+    T: Ord,
+{
+    fn ancestors(&self, x: T) -> Option<BTreeSet<&T>>;
+    fn ancestors_iter(&self, x: T, traversal: Traversal) -> Option<TraversalIter<'_, T>>;
+}
+
+/// `Descendants` returns the set of all vertices to which there is a path from the
+/// vertex x. `descendants_iter` streams the same vertices in breadth first or depth
+/// first order. Both return `None` if x does not exist. Unlike `Prune`, the dag is
+/// left untouched.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Descendants, Traversal};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("waypoint"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("waypoint"));
+/// dag.add_edge(String::from("waypoint"), String::from("destination"));
+///
+/// let descendants = dag.descendants(String::from("origin")).unwrap();
+/// assert_eq!(descendants.len(), 2);
+/// assert!(descendants.contains(&String::from("destination")));
+///
+/// let mut iter = dag
+///     .descendants_iter(String::from("origin"), Traversal::DepthFirst)
+///     .unwrap();
+/// assert_eq!(iter.next(), Some(&String::from("waypoint")));
+/// assert_eq!(iter.next(), Some(&String::from("destination")));
+/// assert_eq!(iter.next(), None);
+/// ```
+pub trait Descendants<T>
+where
+    T: Ord,
+{
+    fn descendants(&self, x: T) -> Option<BTreeSet<&T>>;
+    fn descendants_iter(&self, x: T, traversal: Traversal) -> Option<TraversalIter<'_, T>>;
+}
+
+/// `Reachable` tests whether there is a path from the vertex x to the vertex y,
+/// i.e. whether y is x or a descendant of x. An error is thrown if either x, or y
+/// do not exist.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Reachable};
+/// use btree_dag::Error;
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("waypoint"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("waypoint"));
+/// dag.add_edge(String::from("waypoint"), String::from("destination"));
+///
+/// assert!(dag.has_path(String::from("origin"), String::from("destination")).unwrap());
+/// assert!(!dag.has_path(String::from("destination"), String::from("origin")).unwrap());
+///
+/// let err: Error = dag.has_path(String::from("origin"), String::from("nowhere")).unwrap_err();
+/// assert_eq!(err, Error::VertexDoesNotExist);
+/// ```
+pub trait Reachable<T> {
+    type Error;
+    fn has_path(&self, x: T, y: T) -> Result<bool, Self::Error>;
+}
+
+/// `WouldCreateCycle` tests whether adding an edge from the vertex x to the vertex y
+/// would be rejected for closing a cycle, without modifying the dag. An error is
+/// thrown if either x, or y do not exist.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, WouldCreateCycle};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("destination"));
+///
+/// let origin = String::from("origin");
+/// let destination = String::from("destination");
+/// assert!(dag.would_create_cycle(&destination, &origin).unwrap());
+/// assert!(!dag.would_create_cycle(&origin, &destination).unwrap());
+/// ```
+pub trait WouldCreateCycle<T> {
+    type Error;
+    fn would_create_cycle(&self, x: &T, y: &T) -> Result<bool, Self::Error>;
+}
+
+/// `TransitiveReduction` finds the minimal set of edges with the same reachability
+/// as the dag. An edge from the vertex x to the vertex y is redundant when there is
+/// another path from x to y. `redundant_edges` lists every redundant edge,
+/// `transitive_reduction` returns a copy of the dag without them, and `reduce`
+/// removes them in place, returning the removed edges.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Adjacent, TransitiveReduction};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("waypoint"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("waypoint"));
+/// dag.add_edge(String::from("waypoint"), String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("destination"));
+///
+/// let origin = String::from("origin");
+/// let destination = String::from("destination");
+/// assert_eq!(dag.redundant_edges().len(), 1);
+/// assert!(dag.redundant_edges().contains(&(&origin, &destination)));
+///
+/// let reduced: BTreeDAG<String> = dag.transitive_reduction();
+/// assert!(!reduced.adjacent(origin.clone(), destination.clone()).unwrap());
+/// assert!(reduced.adjacent(origin.clone(), String::from("waypoint")).unwrap());
+///
+/// dag.reduce();
+/// assert_eq!(dag, reduced);
+/// ```
+pub trait TransitiveReduction<T>
+where
+    T: Ord,
+{
+    fn redundant_edges(&self) -> BTreeSet<(&T, &T)>;
+    fn transitive_reduction(&self) -> BTreeDAG<T>;
+    fn reduce(&mut self) -> BTreeSet<(T, T)>;
+}
+
+/// `TransitiveClosure` returns a new dag with an edge from the vertex x to the vertex
+/// y whenever there is a path from x to y in this dag, so that `Adjacent::adjacent`
+/// on the closure answers reachability.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Adjacent, TransitiveClosure};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("waypoint"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("waypoint"));
+/// dag.add_edge(String::from("waypoint"), String::from("destination"));
+///
+/// let closure: BTreeDAG<String> = dag.transitive_closure();
+/// assert!(closure.adjacent(String::from("origin"), String::from("destination")).unwrap());
+/// assert!(!closure.adjacent(String::from("destination"), String::from("origin")).unwrap());
+/// ```
+pub trait TransitiveClosure<T>
+where
+    T: Ord,
+{
+    fn transitive_closure(&self) -> BTreeDAG<T>;
+}
+
+/// `CriticalPath` finds the most expensive path through the dag, where the cost of a
+/// path is the sum of the costs of its vertices (and, for `critical_path_with_edges`,
+/// of its edges). Both return the path and its total cost, or `None` if the dag has
+/// no vertices. A path may start, and end at any vertex, so with negative costs the
+/// most expensive path leaves out any prefix which would lower its cost, and may be a
+/// single vertex. When several paths tie, the path found first in topological order is
+/// returned.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, CriticalPath};
+/// let mut dag: BTreeDAG<&str> = BTreeDAG::new();
+/// dag.add_vertex("fetch");
+/// dag.add_vertex("lint");
+/// dag.add_vertex("build");
+/// dag.add_vertex("test");
+/// dag.add_edge("fetch", "lint");
+/// dag.add_edge("fetch", "build");
+/// dag.add_edge("build", "test");
+/// dag.add_edge("lint", "test");
+///
+/// let duration = |job: &&str| match *job {
+///     "fetch" => 2,
+///     "lint" => 1,
+///     "build" => 10,
+///     _ => 5,
+/// };
+/// let (path, cost) = dag.critical_path(duration).unwrap();
+/// assert_eq!(path, vec![&"fetch", &"build", &"test"]);
+/// assert_eq!(cost, 17);
+///
+/// // Uploading the lint report takes a long time.
+/// let upload = |x: &&str, _: &&str| if *x == "lint" { 20 } else { 0 };
+/// let (path, cost) = dag.critical_path_with_edges(duration, upload).unwrap();
+/// assert_eq!(path, vec![&"fetch", &"lint", &"test"]);
+/// assert_eq!(cost, 28);
+/// ```
+pub trait CriticalPath<T>
+where
+    T: Ord,
+{
+    fn critical_path<C, F>(&self, vertex_cost: F) -> Option<(Vec<&T>, C)>
+    where
+        C: Copy + Ord + Add<Output = C> + Default,
+        F: Fn(&T) -> C;
+    fn critical_path_with_edges<C, F, G>(
+        &self,
+        vertex_cost: F,
+        edge_cost: G,
+    ) -> Option<(Vec<&T>, C)>
+    where
+        C: Copy + Ord + Add<Output = C> + Default,
+        F: Fn(&T) -> C,
+        G: Fn(&T, &T) -> C;
+}
+
+/// `ScheduleAnalysis` computes a PERT schedule from the duration of every vertex: the
+/// earliest and latest start and finish of every vertex, its slack, and the vertices
+/// on the critical path, i.e. those without slack.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, ScheduleAnalysis};
+/// let mut dag: BTreeDAG<&str> = BTreeDAG::new();
+/// dag.add_vertex("design");
+/// dag.add_vertex("docs");
+/// dag.add_vertex("build");
+/// dag.add_vertex("release");
+/// dag.add_edge("design", "docs");
+/// dag.add_edge("design", "build");
+/// dag.add_edge("docs", "release");
+/// dag.add_edge("build", "release");
+///
+/// let schedule = dag.schedule(|task: &&str| match *task {
+///     "design" => 3,
+///     "docs" => 2,
+///     "build" => 5,
+///     _ => 1,
+/// });
+/// assert_eq!(schedule.makespan(), 9);
+///
+/// let docs = schedule.timing(&"docs").unwrap();
+/// assert_eq!(docs.earliest_start, 3);
+/// assert_eq!(docs.latest_start, 6);
+/// assert_eq!(docs.slack, 3);
+///
+/// assert!(schedule.critical_vertices().contains(&"build"));
+/// assert!(!schedule.critical_vertices().contains(&"docs"));
+/// ```
+pub trait ScheduleAnalysis<T>
+where
+    T: Ord,
+{
+    fn schedule<C, F>(&self, duration: F) -> Schedule<'_, T, C>
+    where
+        C: Copy + Ord + Add<Output = C> + Sub<Output = C> + Default,
+        F: Fn(&T) -> C;
+}
+
+/// `ShortestPath` returns a path from the vertex x to the vertex y with the fewest
+/// edges, both x and y inclusive, or `None` if there is no path. When several paths
+/// tie, the least (by `T: Ord`) is preferred at every step. An error is thrown if
+/// either x, or y do not exist.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, ShortestPath};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("waypoint"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("waypoint"));
+/// dag.add_edge(String::from("waypoint"), String::from("destination"));
+///
+/// let path = dag
+///     .shortest_path(String::from("origin"), String::from("destination"))
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(path, vec!["origin", "waypoint", "destination"]);
+///
+/// assert!(dag
+///     .shortest_path(String::from("destination"), String::from("origin"))
+///     .unwrap()
+///     .is_none());
+/// ```
+pub trait ShortestPath<T> {
+    type Error;
+    fn shortest_path(&self, x: T, y: T) -> Result<Option<Vec<T>>, Self::Error>;
+}
+
+/// `AllPaths` enumerates the paths from the vertex x to the vertex y. `paths` lazily
+/// yields every path, each as the list of its vertices from x to y. `count_paths`
+/// counts them without enumerating them, returning `None` if the count does not fit
+/// in a `u64`. Both throw an error if either x, or y do not exist.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, AllPaths};
+/// let mut dag: BTreeDAG<&str> = BTreeDAG::new();
+/// dag.add_vertex("app");
+/// dag.add_vertex("http");
+/// dag.add_vertex("json");
+/// dag.add_vertex("vulnerable");
+/// dag.add_edge("app", "http");
+/// dag.add_edge("app", "json");
+/// dag.add_edge("http", "vulnerable");
+/// dag.add_edge("json", "vulnerable");
+///
+/// let paths: Vec<Vec<&&str>> = dag.paths("app", "vulnerable").unwrap().collect();
+/// assert_eq!(paths, vec![
+///     vec![&"app", &"http", &"vulnerable"],
+///     vec![&"app", &"json", &"vulnerable"],
+/// ]);
+/// assert_eq!(dag.count_paths("app", "vulnerable").unwrap(), Some(2));
+/// ```
+pub trait AllPaths<T>
+where
+    T: Ord,
+{
+    type Error;
+    fn paths(&self, x: T, y: T) -> Result<PathsIter<'_, T>, Self::Error>;
+    fn count_paths(&self, x: T, y: T) -> Result<Option<u64>, Self::Error>;
+}
+
+/// `CommonAncestors` finds the vertices from which there is a path to every one of
+/// the given vertices, where every vertex counts as one of its own ancestors. The
+/// lowest common ancestors are the common ancestors which are not an ancestor of
+/// another common ancestor, matching the semantics of `git merge-base --all`. The
+/// `_of` variants accept any number of vertices. An error is thrown if any vertex
+/// does not exist.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, CommonAncestors};
+/// let mut dag: BTreeDAG<&str> = BTreeDAG::new();
+/// dag.add_vertex("init");
+/// dag.add_vertex("base");
+/// dag.add_vertex("feature");
+/// dag.add_vertex("fix");
+/// dag.add_edge("init", "base");
+/// dag.add_edge("base", "feature");
+/// dag.add_edge("base", "fix");
+///
+/// let common = dag.common_ancestors("feature", "fix").unwrap();
+/// assert_eq!(common.len(), 2);
+/// assert!(common.contains(&"init"));
+///
+/// let lowest = dag.lowest_common_ancestors("feature", "fix").unwrap();
+/// assert_eq!(lowest.len(), 1);
+/// assert!(lowest.contains(&"base"));
+///
+/// let lowest = dag.lowest_common_ancestors_of(vec!["feature", "fix", "base"]).unwrap();
+/// assert!(lowest.contains(&"base"));
+/// ```
+pub trait CommonAncestors<T>
+where
+    T: Ord,
+{
+    type Error;
+    fn common_ancestors(&self, a: T, b: T) -> Result<BTreeSet<&T>, Self::Error>;
+    fn lowest_common_ancestors(&self, a: T, b: T) -> Result<BTreeSet<&T>, Self::Error>;
+    fn common_ancestors_of<I>(&self, vertices: I) -> Result<BTreeSet<&T>, Self::Error>
+    where
+        I: IntoIterator<Item = T>;
+    fn lowest_common_ancestors_of<I>(&self, vertices: I) -> Result<BTreeSet<&T>, Self::Error>
+    where
+        I: IntoIterator<Item = T>;
+}
+
+/// `Dominators` builds the dominator tree of the vertices reachable from a chosen
+/// root, and the post-dominator tree of the vertices which reach a chosen sink. A
+/// vertex which dominates another is a mandatory chokepoint on every path between
+/// the root and it. Both return `None` if the chosen vertex does not exist.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, Dominators};
+/// let mut dag: BTreeDAG<&str> = BTreeDAG::new();
+/// dag.add_vertex("checkout");
+/// dag.add_vertex("build");
+/// dag.add_vertex("unit");
+/// dag.add_vertex("integration");
+/// dag.add_vertex("deploy");
+/// dag.add_edge("checkout", "build");
+/// dag.add_edge("build", "unit");
+/// dag.add_edge("build", "integration");
+/// dag.add_edge("unit", "deploy");
+/// dag.add_edge("integration", "deploy");
+///
+/// let tree = dag.dominator_tree("checkout").unwrap();
+/// assert_eq!(tree.immediate_dominator(&"deploy"), Some(&"build"));
+/// assert!(tree.dominates(&"build", &"unit"));
+/// assert!(!tree.dominates(&"unit", &"deploy"));
+///
+/// let tree = dag.post_dominator_tree("deploy").unwrap();
+/// assert_eq!(tree.immediate_dominator(&"build"), Some(&"deploy"));
+/// assert!(tree.dominates(&"build", &"checkout"));
+/// ```
+pub trait Dominators<T>
+where
+    T: Ord,
+{
+    fn dominator_tree(&self, root: T) -> Option<DominatorTree<'_, T>>;
+    fn post_dominator_tree(&self, sink: T) -> Option<DominatorTree<'_, T>>;
+}
+
+/// `ImpactAnalysis` lists the vertices affected by a set of changed vertices, i.e. the
+/// changed vertices themselves and every vertex reachable from them, in topological
+/// order (ties broken by `T: Ord`). `affected_within` keeps only the vertices which
+/// are also in the set of interesting vertices. The dag is left untouched. An error
+/// is thrown if any of the given vertices do not exist.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, ImpactAnalysis};
+/// let mut dag: BTreeDAG<&str> = BTreeDAG::new();
+/// dag.add_vertex("core");
+/// dag.add_vertex("util");
+/// dag.add_vertex("app");
+/// dag.add_vertex("app_test");
+/// dag.add_vertex("util_test");
+/// dag.add_edge("core", "app");
+/// dag.add_edge("util", "app");
+/// dag.add_edge("util", "util_test");
+/// dag.add_edge("app", "app_test");
+///
+/// let affected = dag.affected(vec!["core"]).unwrap();
+/// assert_eq!(affected, vec![&"core", &"app", &"app_test"]);
+///
+/// let tests = dag
+///     .affected_within(vec!["util"], vec!["app_test", "util_test"])
+///     .unwrap();
+/// assert_eq!(tests, vec![&"app_test", &"util_test"]);
+/// ```
+pub trait ImpactAnalysis<T>
+where
+    T: Ord,
+{
+    type Error;
+    fn affected<I>(&self, changed: I) -> Result<Vec<&T>, Self::Error>
+    where
+        I: IntoIterator<Item = T>;
+    fn affected_within<I, J>(&self, changed: I, interesting: J) -> Result<Vec<&T>, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+        J: IntoIterator<Item = T>;
+}
+
+/// `PreviewRemoval` reports what `RemoveVertex::remove_vertex`, and `Prune::prune_with`
+/// would remove, without removing anything: the vertices, and every edge into or out of
+/// them. An error is thrown if x does not exist, exactly as the removal itself would.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, PreviewRemoval, PruneMode, Vertices};
+/// let mut dag: BTreeDAG<&str> = BTreeDAG::new();
+/// dag.add_vertex("origin");
+/// dag.add_vertex("waypoint");
+/// dag.add_vertex("destination_A");
+/// dag.add_vertex("destination_B");
+/// dag.add_edge("origin", "waypoint");
+/// dag.add_edge("waypoint", "destination_A");
+/// dag.add_edge("waypoint", "destination_B");
+/// dag.add_edge("origin", "destination_B");
+///
+/// let removal = dag.remove_vertex_preview("waypoint").unwrap();
+/// assert_eq!(removal.vertices.len(), 1);
+/// assert!(removal.edges.contains(&(&"origin", &"waypoint")));
+/// assert_eq!(removal.edges.len(), 3);
+///
+/// let removal = dag.prune_preview("waypoint", PruneMode::Orphans).unwrap();
+/// assert!(removal.vertices.contains(&"destination_A"));
+/// assert!(!removal.vertices.contains(&"destination_B"));
+/// assert_eq!(removal.edges.len(), 3);
+///
+/// // Nothing was removed.
+/// assert_eq!(dag.vertices().len(), 4);
+/// ```
+pub trait PreviewRemoval<T>
+where
+    T: Ord,
+{
+    type Error;
+    fn remove_vertex_preview(&self, x: T) -> Result<Removal<'_, T>, Self::Error>;
+    fn prune_preview(&self, x: T, mode: PruneMode) -> Result<Removal<'_, T>, Self::Error>;
+}
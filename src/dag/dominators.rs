@@ -0,0 +1,141 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+
+/// `DominatorTree` records, for every vertex reachable from a chosen root, its
+/// immediate dominator. The vertex a dominates the vertex b if every path from the
+/// root to b passes through a; every vertex dominates itself. The immediate dominator
+/// of b is the dominator of b, other than b, closest to b.
+///
+/// Built by `Dominators::post_dominator_tree`, the tree instead follows the edges
+/// backwards from a chosen sink, so a post-dominates b if every path from b to the
+/// sink passes through a.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct DominatorTree<'a, T>
+where
+    T: Ord,
+{
+    root: &'a T,
+    idoms: BTreeMap<&'a T, &'a T>,
+    depths: BTreeMap<&'a T, usize>,
+}
+
+impl<'a, T> DominatorTree<'a, T>
+where
+    T: Ord,
+{
+    /// Builds the tree of the vertices reachable from the root by following
+    /// `successors`; `predecessors` must hold the same edges reversed.
+    pub(crate) fn new(
+        successors: &'a BTreeMap<T, BTreeSet<T>>,
+        predecessors: &'a BTreeMap<T, BTreeSet<T>>,
+        root: &'a T,
+    ) -> Self {
+        // Find the vertices reachable from the root, and count the edges
+        // into each of them from within that region.
+        let mut in_degrees: BTreeMap<&'a T, usize> = BTreeMap::new();
+        let mut stack: Vec<&'a T> = Vec::new();
+        in_degrees.insert(root, 0);
+        stack.push(root);
+        while let Some(v) = stack.pop() {
+            for w in &successors[v] {
+                match in_degrees.get_mut(w) {
+                    Some(in_degree) => *in_degree += 1,
+                    None => {
+                        in_degrees.insert(w, 1);
+                        stack.push(w);
+                    }
+                }
+            }
+        }
+
+        // In topological order, every predecessor of a vertex already has its
+        // place in the tree, and the immediate dominator of the vertex is the
+        // nearest common ancestor of all of them within the tree.
+        let mut tree = DominatorTree {
+            root,
+            idoms: BTreeMap::new(),
+            depths: BTreeMap::new(),
+        };
+        tree.depths.insert(root, 0);
+        stack.push(root);
+        while let Some(v) = stack.pop() {
+            if v != root {
+                let idom = predecessors[v]
+                    .iter()
+                    .filter(|u| tree.depths.contains_key(*u))
+                    .fold(None, |idom: Option<&'a T>, u| match idom {
+                        Some(idom) => Some(tree.intersect(idom, u)),
+                        None => Some(u),
+                    })
+                    .unwrap();
+                tree.depths.insert(v, tree.depths[idom] + 1);
+                tree.idoms.insert(v, idom);
+            }
+            for w in &successors[v] {
+                let in_degree = in_degrees.get_mut(w).unwrap();
+                *in_degree -= 1;
+                if *in_degree == 0 {
+                    stack.push(w);
+                }
+            }
+        }
+        tree
+    }
+
+    /// Walks a and b up the tree until they meet.
+    fn intersect(&self, mut a: &'a T, mut b: &'a T) -> &'a T {
+        while a != b {
+            if self.depths[a] >= self.depths[b] {
+                a = self.idoms[a];
+            } else {
+                b = self.idoms[b];
+            }
+        }
+        a
+    }
+
+    pub fn root(&self) -> &'a T {
+        self.root
+    }
+
+    /// Returns the immediate dominator of the vertex x, or `None` if x is the
+    /// root, or is not reachable from the root.
+    pub fn immediate_dominator(&self, x: &T) -> Option<&'a T> {
+        self.idoms.get(x).copied()
+    }
+
+    /// Tests whether the vertex a dominates the vertex b. Returns false if either
+    /// is not reachable from the root.
+    pub fn dominates(&self, a: &T, b: &T) -> bool {
+        match (self.depths.get(a), self.depths.get_key_value(b)) {
+            (Some(&depth_a), Some((&b, &depth_b))) => {
+                // Walk b up to the depth of a; a dominates b if they meet.
+                let mut b: &T = b;
+                for _ in depth_a..depth_b {
+                    b = self.idoms[b];
+                }
+                b == a
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns every dominator of the vertex x, from x itself up to the root,
+    /// or `None` if x is not reachable from the root.
+    pub fn dominators(&self, x: &T) -> Option<Vec<&'a T>> {
+        let (&x, _) = self.depths.get_key_value(x)?;
+        let mut x: &'a T = x;
+        let mut dominators: Vec<&'a T> = Vec::new();
+        dominators.push(x);
+        while let Some(idom) = self.idoms.get(x) {
+            dominators.push(idom);
+            x = idom;
+        }
+        Some(dominators)
+    }
+
+    /// Returns the vertices reachable from the root, i.e. those in the tree.
+    pub fn vertices(&self) -> BTreeSet<&'a T> {
+        self.depths.keys().copied().collect()
+    }
+}
@@ -0,0 +1,447 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::default::Default;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::dag::*;
+use crate::{EdgeError, Error};
+
+/// `IncrementalBTreeDAG` is a `BTreeDAG` which maintains a topological order of its
+/// vertices as edges are added, using the algorithm of Pearce and Kelly. When an edge
+/// (x, y) is added and x already precedes y, nothing is searched at all. Otherwise only
+/// the vertices positioned between y and x are searched, and only those found are
+/// reordered. The current topological order is therefore always available in O(V).
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{IncrementalBTreeDAG, AddVertex, AddEdge};
+/// let mut dag: IncrementalBTreeDAG<String> = IncrementalBTreeDAG::new();
+/// dag.add_vertex(String::from("shoes"));
+/// dag.add_vertex(String::from("socks"));
+/// dag.add_edge(String::from("socks"), String::from("shoes")).unwrap();
+///
+/// assert_eq!(dag.topological_order(), vec!["socks", "shoes"]);
+/// ```
+///
+/// Only the dag is serialized. When deserializing, the order is rebuilt as it is by
+/// `From<BTreeDAG<T>>`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct IncrementalBTreeDAG<T>
+where
+    T: Ord,
+{
+    dag: BTreeDAG<T>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    positions: BTreeMap<T, usize>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    order: BTreeMap<usize, T>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    next_position: usize,
+}
+
+impl<T> IncrementalBTreeDAG<T>
+where
+    T: Ord,
+{
+    pub fn new() -> Self {
+        IncrementalBTreeDAG {
+            dag: BTreeDAG::new(),
+            positions: BTreeMap::new(),
+            order: BTreeMap::new(),
+            next_position: 0,
+        }
+    }
+
+    /// Returns the underlying `BTreeDAG`, for use with the read only traits.
+    pub fn as_dag(&self) -> &BTreeDAG<T> {
+        &self.dag
+    }
+
+    /// Consumes the `IncrementalBTreeDAG`, discarding the topological order.
+    pub fn into_dag(self) -> BTreeDAG<T> {
+        self.dag
+    }
+
+    /// Returns the vertices in the maintained topological order.
+    pub fn topological_order(&self) -> Vec<&T> {
+        self.order.values().collect()
+    }
+
+    /// Returns the position of the vertex x in the maintained topological order.
+    /// Positions are only meaningful relative to one another.
+    pub fn position(&self, x: &T) -> Option<usize> {
+        self.positions.get(x).copied()
+    }
+
+    /// Searches forwards from y through the vertices positioned strictly before
+    /// `upper_bound`, i.e. the position of x. If x is found, i.e. (x, y) closes a
+    /// cycle, the path from y to x is returned as the error. Both x and y must
+    /// exist.
+    fn forward_region(&self, x: &T, y: &T, upper_bound: usize) -> Result<Vec<&T>, Vec<&T>> {
+        let (y, _) = self.dag.vertices.get_key_value(y).unwrap();
+        if x == y {
+            return Err(vec![y]);
+        }
+        let mut region: Vec<&T> = Vec::new();
+        let mut predecessors: BTreeMap<&T, &T> = BTreeMap::new();
+        let mut visited: BTreeSet<&T> = BTreeSet::new();
+        let mut stack: Vec<&T> = Vec::new();
+        visited.insert(y);
+        stack.push(y);
+        while let Some(v) = stack.pop() {
+            region.push(v);
+            for w in &self.dag.vertices[v] {
+                if w == x {
+                    let mut path: Vec<&T> = Vec::new();
+                    path.push(w);
+                    let mut u = v;
+                    path.push(u);
+                    while let Some(p) = predecessors.get(u) {
+                        path.push(p);
+                        u = p;
+                    }
+                    path.reverse();
+                    return Err(path);
+                }
+                if self.positions[w] < upper_bound && visited.insert(w) {
+                    predecessors.insert(w, v);
+                    stack.push(w);
+                }
+            }
+        }
+        Ok(region)
+    }
+
+    /// Searches backwards from x through the vertices positioned strictly after
+    /// `lower_bound`, i.e. the position of y.
+    fn backward_region(&self, x: &T, lower_bound: usize) -> Vec<&T> {
+        let mut region: Vec<&T> = Vec::new();
+        let mut visited: BTreeSet<&T> = BTreeSet::new();
+        let mut stack: Vec<&T> = Vec::new();
+        if let Some((x, _)) = self.dag.parents.get_key_value(x) {
+            visited.insert(x);
+            stack.push(x);
+        }
+        while let Some(v) = stack.pop() {
+            region.push(v);
+            for w in &self.dag.parents[v] {
+                if self.positions[w] > lower_bound && visited.insert(w) {
+                    stack.push(w);
+                }
+            }
+        }
+        region
+    }
+}
+
+impl<T> IncrementalBTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    /// Moves every vertex of the backward region ahead of every vertex of the
+    /// forward region, reusing the positions the two regions already occupy.
+    fn reorder(&mut self, mut backward: Vec<T>, mut forward: Vec<T>) {
+        backward.sort_by_key(|v| self.positions[v]);
+        forward.sort_by_key(|v| self.positions[v]);
+        let mut pool: Vec<usize> = backward
+            .iter()
+            .chain(forward.iter())
+            .map(|v| self.positions[v])
+            .collect();
+        pool.sort_unstable();
+        for (v, position) in backward.into_iter().chain(forward).zip(pool) {
+            self.order.insert(position, v.clone());
+            self.positions.insert(v, position);
+        }
+    }
+}
+
+/// Two dags are equal if they have the same vertices, and edges, whichever valid
+/// topological order each maintains.
+impl<T> PartialEq for IncrementalBTreeDAG<T>
+where
+    T: Ord,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.dag == other.dag
+    }
+}
+
+impl<T> Eq for IncrementalBTreeDAG<T> where T: Ord {}
+
+impl<T> Default for IncrementalBTreeDAG<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The initial order is the one given by `TopologicalSort`.
+impl<T> From<BTreeDAG<T>> for IncrementalBTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    fn from(dag: BTreeDAG<T>) -> Self {
+        let mut positions: BTreeMap<T, usize> = BTreeMap::new();
+        let mut order: BTreeMap<usize, T> = BTreeMap::new();
+        for (position, v) in dag.topological_iter().enumerate() {
+            positions.insert(v.clone(), position);
+            order.insert(position, v.clone());
+        }
+        let next_position = order.len();
+        IncrementalBTreeDAG {
+            dag,
+            positions,
+            order,
+            next_position,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for IncrementalBTreeDAG<T>
+where
+    T: Ord + Clone + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "IncrementalBTreeDAG")]
+        #[serde(bound = "T: Ord + Clone + Deserialize<'de>")]
+        struct Wrapped<T>
+        where
+            T: Ord,
+        {
+            dag: BTreeDAG<T>,
+        }
+
+        let Wrapped { dag } = Wrapped::<T>::deserialize(deserializer)?;
+        Ok(IncrementalBTreeDAG::from(dag))
+    }
+}
+
+impl<T> Vertices<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord,
+{
+    fn vertices(&self) -> BTreeSet<&T> {
+        self.dag.vertices()
+    }
+}
+
+/// A new vertex has no edges, and so it is placed at the end of the order.
+impl<T> AddVertex<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    fn add_vertex(&mut self, x: T) -> Option<BTreeSet<T>> {
+        if !self.positions.contains_key(&x) {
+            self.positions.insert(x.clone(), self.next_position);
+            self.order.insert(self.next_position, x.clone());
+            self.next_position += 1;
+        }
+        self.dag.add_vertex(x)
+    }
+}
+
+impl<T> AddEdge<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    type Error = EdgeError<T>;
+    fn add_edge(&mut self, x: T, y: T) -> Result<BTreeSet<T>, Self::Error> {
+        match (self.position(&x), self.position(&y)) {
+            (Some(position_x), Some(position_y)) => {
+                if position_x >= position_y {
+                    // y does not come after x, so only the vertices between
+                    // them in the order can be affected.
+                    let forward: Vec<T> = self
+                        .forward_region(&x, &y, position_x)
+                        .map_err(|path| EdgeError::Cycle(path.into_iter().cloned().collect()))?
+                        .into_iter()
+                        .cloned()
+                        .collect();
+                    let backward: Vec<T> = self
+                        .backward_region(&x, position_y)
+                        .into_iter()
+                        .cloned()
+                        .collect();
+                    self.reorder(backward, forward);
+                }
+                Ok(self.dag.insert_edge(x, y))
+            }
+            _ => Err(EdgeError::VertexDoesNotExist),
+        }
+    }
+}
+
+impl<T> GetVertexValue<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord,
+{
+    fn get_vertex_value(&self, v: T) -> Option<&BTreeSet<T>> {
+        self.dag.get_vertex_value(v)
+    }
+}
+
+/// Removing an edge cannot invalidate the order.
+impl<T> RemoveEdge<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    type Error = Error;
+    fn remove_edge(&mut self, x: T, y: T) -> Result<BTreeSet<T>, Self::Error> {
+        self.dag.remove_edge(x, y)
+    }
+}
+
+impl<T> RemoveVertex<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    type Error = Error;
+    fn remove_vertex(&mut self, x: T) -> Result<BTreeSet<T>, Self::Error> {
+        if let Some(position) = self.positions.remove(&x) {
+            self.order.remove(&position);
+        }
+        self.dag.remove_vertex(x)
+    }
+}
+
+/// Like `remove_vertex`, pruning cannot invalidate the order.
+impl<T> Prune<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    type Error = Error;
+    fn prune(&mut self, x: T) -> Result<BTreeSet<T>, Self::Error> {
+        self.prune_with(x, PruneMode::Cascade)
+    }
+
+    fn prune_with(&mut self, x: T, mode: PruneMode) -> Result<BTreeSet<T>, Self::Error> {
+        let removed = self.dag.prune_with(x, mode)?;
+        for v in &removed {
+            if let Some(position) = self.positions.remove(v) {
+                self.order.remove(&position);
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// z is placed at the end of the order, and then moved ahead of y, along with the
+/// vertices between them which y reaches.
+impl<T> SplitEdge<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    type Error = Error;
+    fn split_edge(&mut self, x: T, y: T, z: T) -> Result<(), Self::Error> {
+        if !self.dag.adjacent(x.clone(), y.clone())? {
+            return Err(Error::EdgeDoesNotExist);
+        }
+        if self.positions.contains_key(&z) {
+            return Err(Error::VertexExists);
+        }
+
+        // Neither edge can close a cycle, since z is new.
+        self.add_vertex(z.clone());
+        self.add_edge(x.clone(), z.clone())?;
+        self.add_edge(z, y.clone())?;
+        self.remove_edge(x, y)?;
+        Ok(())
+    }
+}
+
+/// The merged vertex is placed at the end of the order, and its edges are then
+/// added by `add_edge`, moving it ahead of its children.
+impl<T> MergeVertices<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    type Error = EdgeError<T>;
+    fn merge_vertices(&mut self, a: T, b: T, into: T) -> Result<(), Self::Error> {
+        let (parents, children) = self.dag.merge_edges(&a, &b, &into)?;
+        // Both exist, so neither removal can fail, but b is already gone if it
+        // is a.
+        let _ = self.remove_vertex(a);
+        let _ = self.remove_vertex(b);
+        self.add_vertex(into.clone());
+        // The merge was checked for cycles, so none of the edges can fail.
+        for u in parents {
+            self.add_edge(u, into.clone())?;
+        }
+        for w in children {
+            self.add_edge(into.clone(), w)?;
+        }
+        Ok(())
+    }
+}
+
+/// Every bypass edge runs from a vertex before x to a vertex after x, and so the
+/// order remains valid.
+impl<T> RemoveVertexBypass<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    type Error = Error;
+    fn remove_vertex_bypass(&mut self, x: T) -> Result<BTreeSet<(T, T)>, Self::Error> {
+        if let Some(position) = self.positions.remove(&x) {
+            self.order.remove(&position);
+        }
+        self.dag.remove_vertex_bypass(x)
+    }
+}
+
+impl<T> Adjacent<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord,
+{
+    type Error = Error;
+    fn adjacent(&self, x: T, y: T) -> Result<bool, Self::Error> {
+        self.dag.adjacent(x, y)
+    }
+}
+
+impl<T> Connections<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord,
+{
+    fn connections(&self, x: T) -> Option<&BTreeSet<T>> {
+        self.dag.connections(x)
+    }
+}
+
+impl<T> Parents<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord,
+{
+    fn parents(&self, y: T) -> Option<&BTreeSet<T>> {
+        self.dag.parents(y)
+    }
+}
+
+/// Like `add_edge`, the search is skipped entirely when x already precedes y.
+impl<T> WouldCreateCycle<T> for IncrementalBTreeDAG<T>
+where
+    T: Ord,
+{
+    type Error = Error;
+    fn would_create_cycle(&self, x: &T, y: &T) -> Result<bool, Self::Error> {
+        match (self.position(x), self.position(y)) {
+            (Some(position_x), Some(position_y)) => {
+                Ok(position_x >= position_y && self.forward_region(x, y, position_x).is_err())
+            }
+            _ => Err(Error::VertexDoesNotExist),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use alloc::collections::{btree_set, BTreeMap, BTreeSet, VecDeque};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::dag::BTreeDAG;
+
+/// `TopologicalIter` lazily yields the vertices of a dag in topological order
+/// using Kahn's algorithm. Whenever more than one vertex is ready to be yielded,
+/// the least vertex (by `T: Ord`) is yielded first, so the ordering is deterministic.
+pub struct TopologicalIter<'a, T>
+where
+    T: Ord,
+{
+    vertices: &'a BTreeMap<T, BTreeSet<T>>,
+    in_degrees: BTreeMap<&'a T, usize>,
+    ready: BTreeSet<&'a T>,
+}
+
+impl<'a, T> TopologicalIter<'a, T>
+where
+    T: Ord,
+{
+    pub(crate) fn new(dag: &'a BTreeDAG<T>) -> Self {
+        Self::with_in_degrees(dag, dag.in_degrees())
+    }
+
+    /// Orders only the vertices of `region`, ignoring every edge into the region
+    /// from outside of it.
+    pub(crate) fn within(dag: &'a BTreeDAG<T>, region: &BTreeSet<&'a T>) -> Self {
+        let in_degrees: BTreeMap<&'a T, usize> = region
+            .iter()
+            .map(|v| {
+                let in_degree = dag.parents[*v]
+                    .iter()
+                    .filter(|u| region.contains(u))
+                    .count();
+                (*v, in_degree)
+            })
+            .collect();
+        Self::with_in_degrees(dag, in_degrees)
+    }
+
+    fn with_in_degrees(dag: &'a BTreeDAG<T>, in_degrees: BTreeMap<&'a T, usize>) -> Self {
+        let ready: BTreeSet<&'a T> = in_degrees
+            .iter()
+            .filter(|(_, in_degree)| **in_degree == 0)
+            .map(|(v, _)| *v)
+            .collect();
+        TopologicalIter {
+            vertices: &dag.vertices,
+            in_degrees,
+            ready,
+        }
+    }
+}
+
+impl<'a, T> Iterator for TopologicalIter<'a, T>
+where
+    T: Ord,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.ready.pop_first()?;
+        if let Some(adj_x) = self.vertices.get(x) {
+            for y in adj_x {
+                if let Some(in_degree) = self.in_degrees.get_mut(y) {
+                    *in_degree -= 1;
+                    if *in_degree == 0 {
+                        self.ready.insert(y);
+                    }
+                }
+            }
+        }
+        Some(x)
+    }
+}
+
+/// `Traversal` selects the order in which a `TraversalIter` visits vertices.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Traversal {
+    /// Visit every vertex at distance k before any vertex at distance k + 1.
+    BreadthFirst,
+    /// Visit the vertices in depth first pre-order.
+    DepthFirst,
+}
+
+/// `TraversalIter` lazily yields every vertex reachable from a starting vertex
+/// (excluding the starting vertex itself) by following edges in one direction,
+/// either forwards through the adjacency list or backwards through the reverse
+/// adjacency list. Each vertex is yielded once, and neighbours are visited in
+/// ascending order.
+pub struct TraversalIter<'a, T>
+where
+    T: Ord,
+{
+    edges: &'a BTreeMap<T, BTreeSet<T>>,
+    traversal: Traversal,
+    frontier: VecDeque<&'a T>,
+    visited: BTreeSet<&'a T>,
+}
+
+impl<'a, T> TraversalIter<'a, T>
+where
+    T: Ord,
+{
+    pub(crate) fn new(edges: &'a BTreeMap<T, BTreeSet<T>>, x: &'a T, traversal: Traversal) -> Self {
+        let mut iter = TraversalIter {
+            edges,
+            traversal,
+            frontier: VecDeque::new(),
+            visited: BTreeSet::new(),
+        };
+        iter.visited.insert(x);
+        iter.expand(x);
+        iter
+    }
+
+    fn expand(&mut self, x: &'a T) {
+        if let Some(adj_x) = self.edges.get(x) {
+            match self.traversal {
+                // Vertices are marked as visited when they are discovered, so
+                // that each vertex enters the queue at most once.
+                Traversal::BreadthFirst => {
+                    for y in adj_x {
+                        if self.visited.insert(y) {
+                            self.frontier.push_back(y);
+                        }
+                    }
+                }
+                // Vertices are marked as visited when they are popped, and
+                // neighbours are pushed in reverse so the least is popped first.
+                Traversal::DepthFirst => {
+                    for y in adj_x.iter().rev() {
+                        if !self.visited.contains(y) {
+                            self.frontier.push_back(y);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T> Iterator for TraversalIter<'a, T>
+where
+    T: Ord,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = match self.traversal {
+            Traversal::BreadthFirst => self.frontier.pop_front()?,
+            Traversal::DepthFirst => loop {
+                let x = self.frontier.pop_back()?;
+                if self.visited.insert(x) {
+                    break x;
+                }
+            },
+        };
+        self.expand(x);
+        Some(x)
+    }
+}
+
+/// `PathsIter` lazily yields every path from one vertex to another, each as the list
+/// of its vertices, both ends inclusive. Every path in a dag is simple. The search
+/// only descends into vertices from which the destination can be reached, so no work
+/// is wasted on dead ends. Paths are yielded in lexicographic order.
+pub struct PathsIter<'a, T>
+where
+    T: Ord,
+{
+    edges: &'a BTreeMap<T, BTreeSet<T>>,
+    y: &'a T,
+    useful: BTreeSet<&'a T>,
+    stack: Vec<(&'a T, btree_set::Iter<'a, T>)>,
+    trivial: Option<&'a T>,
+}
+
+impl<'a, T> PathsIter<'a, T>
+where
+    T: Ord,
+{
+    /// `useful` must hold y and every vertex from which y can be reached.
+    pub(crate) fn new(
+        edges: &'a BTreeMap<T, BTreeSet<T>>,
+        x: &'a T,
+        y: &'a T,
+        useful: BTreeSet<&'a T>,
+    ) -> Self {
+        let mut stack: Vec<(&'a T, btree_set::Iter<'a, T>)> = Vec::new();
+        let mut trivial: Option<&'a T> = None;
+        if x == y {
+            trivial = Some(x);
+        } else if useful.contains(x) {
+            stack.push((x, edges[x].iter()));
+        }
+        PathsIter {
+            edges,
+            y,
+            useful,
+            stack,
+            trivial,
+        }
+    }
+}
+
+impl<'a, T> Iterator for PathsIter<'a, T>
+where
+    T: Ord,
+{
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(x) = self.trivial.take() {
+            return Some(vec![x]);
+        }
+        loop {
+            let (_, children) = self.stack.last_mut()?;
+            match children.next() {
+                Some(w) if w == self.y => {
+                    let mut path: Vec<&'a T> = self.stack.iter().map(|(v, _)| *v).collect();
+                    path.push(w);
+                    return Some(path);
+                }
+                Some(w) if self.useful.contains(w) => {
+                    self.stack.push((w, self.edges[w].iter()));
+                }
+                Some(_) => {}
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
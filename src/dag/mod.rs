@@ -1,24 +1,45 @@
 mod api;
+mod dominators;
+mod incremental;
+mod iter;
+mod preview;
+mod reachability;
+mod schedule;
 mod test;
 
-use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::vec;
+use alloc::vec::Vec;
 use core::default::Default;
+use core::ops::{Add, Sub};
 
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize};
 
-use crate::Error;
+use crate::{EdgeError, Error};
 pub use api::*;
+pub use dominators::*;
+pub use incremental::*;
+pub use iter::*;
+pub use preview::*;
+pub use reachability::*;
+pub use schedule::*;
 
 /// `BTreeDAG` is an implementation of a directed acyclic graph (abstract data structure)
-/// which utilizes `BTreeMap` for the vertex adjacency list.
+/// which utilizes `BTreeMap` for the vertex adjacency list. A second `BTreeMap` maintains
+/// the reverse adjacency list, i.e. for every vertex the set of vertices pointing at it.
+///
+/// Only the adjacency list is serialized. The reverse adjacency list is rebuilt when
+/// deserializing, which fails if an edge points at a missing vertex, or closes a cycle.
 #[derive(PartialEq, Eq, Clone, Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct BTreeDAG<T>
 where
     T: Ord,
 {
     vertices: BTreeMap<T, BTreeSet<T>>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
+    parents: BTreeMap<T, BTreeSet<T>>,
 }
 
 impl<T> BTreeDAG<T>
@@ -27,28 +48,187 @@ where
 {
     pub fn new() -> Self {
         let vertices: BTreeMap<T, BTreeSet<T>> = BTreeMap::new();
-        BTreeDAG { vertices }
+        let parents: BTreeMap<T, BTreeSet<T>> = BTreeMap::new();
+        BTreeDAG { vertices, parents }
     }
 
-    fn cyclic_relationship_exists(&self, x: &T, y: &T) -> Result<(), Error> {
-        if let Some(adj_y) = self.vertices.get(y) {
-            // If y has adjacent vertices, then have we need to
-            // check if x exists in these adjacent vertices;
-            if !adj_y.contains(x) {
-                // if it does not, then recurse. Making sure x
-                // is not adjacent to any of y's adjacent vertices.
-                for adj in adj_y {
-                    self.cyclic_relationship_exists(x, adj)?;
+    /// Counts, for every vertex, the number of edges pointing at it.
+    fn in_degrees(&self) -> BTreeMap<&T, usize> {
+        self.parents
+            .iter()
+            .map(|(v, parents_v)| (v, parents_v.len()))
+            .collect()
+    }
+
+    /// Collects every vertex reachable from the seeds, seeds included, following
+    /// `edges`, which is either the adjacency list or the reverse adjacency list.
+    fn reach<'a, I>(edges: &'a BTreeMap<T, BTreeSet<T>>, seeds: I) -> BTreeSet<&'a T>
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        let mut visited: BTreeSet<&T> = BTreeSet::new();
+        let mut stack: Vec<&T> = Vec::new();
+        for v in seeds {
+            if visited.insert(v) {
+                stack.push(v);
+            }
+        }
+        while let Some(v) = stack.pop() {
+            if let Some(adj_v) = edges.get(v) {
+                for w in adj_v {
+                    if visited.insert(w) {
+                        stack.push(w);
+                    }
                 }
-                // If no error has been thrown by this line, then
-                // we must not have found x in any of the adjacency lists.
-                return Ok(());
             }
-            return Err(Error::EdgeExists);
         }
-        // If y has no adjacent vertices, then we can be sure there
-        // no circular relationship.
-        Err(Error::VertexDoesNotExist)
+        visited
+    }
+
+    /// Collects the vertices `Prune::prune_with` removes: x, and its descendants
+    /// selected by the mode. Returns `None` if x does not exist.
+    fn prune_set(&self, x: &T, mode: PruneMode) -> Option<BTreeSet<&T>> {
+        let (x, _) = self.vertices.get_key_value(x)?;
+        let descendants = Self::reach(&self.vertices, Some(x));
+        match mode {
+            PruneMode::Cascade => Some(descendants),
+            PruneMode::Orphans => {
+                // In topological order, all parents of a vertex are decided
+                // before it is. Parents outside of the descendants of x are
+                // never removed.
+                let mut removed: BTreeSet<&T> = BTreeSet::new();
+                for v in TopologicalIter::within(self, &descendants) {
+                    if v == x || self.parents[v].iter().all(|u| removed.contains(u)) {
+                        removed.insert(v);
+                    }
+                }
+                Some(removed)
+            }
+        }
+    }
+
+    /// Collects the edges into or out of the given vertices, which all exist.
+    fn removal<'a>(&'a self, vertices: BTreeSet<&'a T>) -> Removal<'a, T> {
+        let mut edges: BTreeSet<(&T, &T)> = BTreeSet::new();
+        for &v in &vertices {
+            edges.extend(self.vertices[v].iter().map(|w| (v, w)));
+            edges.extend(self.parents[v].iter().map(|u| (u, v)));
+        }
+        Removal { vertices, edges }
+    }
+
+    /// Searches depth first from x for y, visiting each vertex and edge at most
+    /// once, and returns the path found from x to y, both inclusive. Every vertex
+    /// reaches itself by the path `[x]`. Returns `None` if x does not exist.
+    fn find_path(&self, x: &T, y: &T) -> Option<Vec<&T>> {
+        self.find_path_avoiding(x, y, false)
+    }
+
+    /// Like `find_path`, but if `avoid_edge` is set the edge (x, y) itself is
+    /// not followed, so only a longer path from x to y is found.
+    fn find_path_avoiding(&self, x: &T, y: &T, avoid_edge: bool) -> Option<Vec<&T>> {
+        let (x, _) = self.vertices.get_key_value(x)?;
+        // Every discovered vertex, other than x, records the vertex it was
+        // discovered from, so the path can be walked back from y.
+        let mut predecessors: BTreeMap<&T, &T> = BTreeMap::new();
+        let mut visited: BTreeSet<&T> = BTreeSet::new();
+        let mut stack: Vec<&T> = Vec::new();
+        visited.insert(x);
+        stack.push(x);
+        while let Some(v) = stack.pop() {
+            if v == y {
+                let mut path: Vec<&T> = Vec::new();
+                path.push(v);
+                let mut v = v;
+                while let Some(u) = predecessors.get(v) {
+                    path.push(u);
+                    v = u;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for w in &self.vertices[v] {
+                if avoid_edge && v == x && w == y {
+                    continue;
+                }
+                if visited.insert(w) {
+                    predecessors.insert(w, v);
+                    stack.push(w);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<T> BTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    fn cyclic_relationship_exists(&self, x: &T, y: &T) -> Result<(), EdgeError<T>> {
+        if !self.vertices.contains_key(y) {
+            return Err(EdgeError::VertexDoesNotExist);
+        }
+        // The edge (x, y) closes a cycle if, and only if, there already is a
+        // path from y to x. The search visits each vertex and edge at most
+        // once, so the check is O(V + E) in the worst case.
+        if let Some(path) = self.find_path(y, x) {
+            return Err(EdgeError::Cycle(path.into_iter().cloned().collect()));
+        }
+        Ok(())
+    }
+
+    /// Checks that a, and b can be merged into the vertex named into, and returns
+    /// the parents, and children the merged vertex will have.
+    fn merge_edges(
+        &self,
+        a: &T,
+        b: &T,
+        into: &T,
+    ) -> Result<(BTreeSet<T>, BTreeSet<T>), EdgeError<T>> {
+        if !self.vertices.contains_key(a) || !self.vertices.contains_key(b) {
+            return Err(EdgeError::VertexDoesNotExist);
+        }
+        if into != a && into != b && self.vertices.contains_key(into) {
+            return Err(EdgeError::VertexExists);
+        }
+        // An edge between a, and b is contracted, but any longer path between
+        // them would become a cycle through the merged vertex.
+        if a != b {
+            if let Some(path) = self
+                .find_path_avoiding(a, b, true)
+                .or_else(|| self.find_path_avoiding(b, a, true))
+            {
+                return Err(EdgeError::Cycle(path.into_iter().cloned().collect()));
+            }
+        }
+        let is_merged = |v: &&T| *v != a && *v != b;
+        let parents: BTreeSet<T> = self.parents[a]
+            .iter()
+            .chain(self.parents[b].iter())
+            .filter(is_merged)
+            .cloned()
+            .collect();
+        let children: BTreeSet<T> = self.vertices[a]
+            .iter()
+            .chain(self.vertices[b].iter())
+            .filter(is_merged)
+            .cloned()
+            .collect();
+        Ok((parents, children))
+    }
+
+    /// Inserts the edge (x, y) without checking for cycles, and returns x's
+    /// previous adjacency list. Both x and y must exist.
+    fn insert_edge(&mut self, x: T, y: T) -> BTreeSet<T> {
+        // Add y to x's adjacency list.
+        let mut adj_x: BTreeSet<T> = self.vertices[&x].clone();
+        adj_x.insert(y.clone());
+
+        // Add x to y's reverse adjacency list.
+        self.parents.get_mut(&y).unwrap().insert(x.clone());
+
+        self.vertices.insert(x, adj_x).unwrap()
     }
 }
 
@@ -61,6 +241,47 @@ where
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for BTreeDAG<T>
+where
+    T: Ord + Clone + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "BTreeDAG")]
+        struct Adjacency<T>
+        where
+            T: Ord,
+        {
+            vertices: BTreeMap<T, BTreeSet<T>>,
+        }
+
+        let Adjacency { vertices } = Adjacency::<T>::deserialize(deserializer)?;
+        let mut parents: BTreeMap<T, BTreeSet<T>> = vertices
+            .keys()
+            .map(|v| (v.clone(), BTreeSet::new()))
+            .collect();
+        for (v, adj_v) in &vertices {
+            for w in adj_v {
+                parents
+                    .get_mut(w)
+                    .ok_or_else(|| de::Error::custom("edge to a vertex which does not exist"))?
+                    .insert(v.clone());
+            }
+        }
+        let dag = BTreeDAG { vertices, parents };
+        // Every vertex of a dag is eventually sorted, but those on a cycle
+        // never are.
+        if dag.topological_iter().count() != dag.vertices.len() {
+            return Err(de::Error::custom("edges form a cycle"));
+        }
+        Ok(dag)
+    }
+}
+
 impl<T> Vertices<T> for BTreeDAG<T>
 where
     T: Ord,
@@ -70,12 +291,32 @@ where
     }
 }
 
+/// Adding a vertex which already exists clears its adjacency list, and so x must
+/// also be removed from the reverse adjacency list of each of its former children.
+/// Every vertex is a key of both the adjacency list, and the reverse adjacency list,
+/// so `T: Clone` is required, though x is only cloned when it is new.
 impl<T> AddVertex<T> for BTreeDAG<T>
 where
-    T: Ord,
+    T: Ord + Clone,
 {
     fn add_vertex(&mut self, x: T) -> Option<BTreeSet<T>> {
-        self.vertices.insert(x, BTreeSet::new())
+        match self.vertices.get_mut(&x) {
+            Some(adj_x) => {
+                // Re-adding x clears its edges to its children.
+                let adj_x = core::mem::take(adj_x);
+                for y in &adj_x {
+                    if let Some(parents_y) = self.parents.get_mut(y) {
+                        parents_y.remove(&x);
+                    }
+                }
+                Some(adj_x)
+            }
+            None => {
+                self.parents.insert(x.clone(), BTreeSet::new());
+                self.vertices.insert(x, BTreeSet::new());
+                None
+            }
+        }
     }
 }
 
@@ -84,17 +325,13 @@ impl<T> AddEdge<T> for BTreeDAG<T>
 where
     T: Ord + Clone,
 {
-    type Error = Error;
+    type Error = EdgeError<T>;
     fn add_edge(&mut self, x: T, y: T) -> Result<BTreeSet<T>, Self::Error> {
-        if let Some(adj_x) = self.vertices.get(&x) {
+        if self.vertices.contains_key(&x) {
             self.cyclic_relationship_exists(&x, &y)?;
-            // Add y to x's adjacency list.
-            let mut adj_x: BTreeSet<T> = adj_x.clone();
-            adj_x.insert(y);
-
-            return Ok(self.vertices.insert(x, adj_x).unwrap());
+            return Ok(self.insert_edge(x, y));
         }
-        Err(Error::VertexDoesNotExist)
+        Err(EdgeError::VertexDoesNotExist)
     }
 }
 
@@ -115,12 +352,17 @@ where
 {
     type Error = Error;
     fn remove_edge(&mut self, x: T, y: T) -> Result<BTreeSet<T>, Self::Error> {
-        if self.vertices.get(&y).is_some() {
+        if self.vertices.contains_key(&y) {
             if let Some(adj_x) = self.vertices.get(&x) {
                 // Remove y from x's adjacency list.
                 let mut updated_adj_x = adj_x.clone();
                 updated_adj_x.remove(&y);
 
+                // Remove x from y's reverse adjacency list.
+                if let Some(parents_y) = self.parents.get_mut(&y) {
+                    parents_y.remove(&x);
+                }
+
                 // Update vertices. Since we have already verified x is in vertices,
                 // we can safely unwrap.
                 return Ok(self.vertices.insert(x, updated_adj_x).unwrap());
@@ -137,22 +379,104 @@ where
 {
     type Error = Error;
     fn remove_vertex(&mut self, x: T) -> Result<BTreeSet<T>, Self::Error> {
-        // The remove edge method will error with EdgeDoesNotExist on the
-        // first iteration is in fact x does not exist.
-        self.vertices
-            .clone()
-            .into_iter()
-            .filter(|v| -> bool { v.1.contains(&x) })
-            .try_for_each(|v| -> Result<(), Self::Error> {
-                self.remove_edge(v.0, x.clone())?;
-                Ok(())
-            })?;
-        // At this point, no other vertices should point to x,
-        // and so x can be removed.
+        if let Some(parents_x) = self.parents.remove(&x) {
+            // Remove x from the adjacency list of every vertex pointing at x.
+            for v in parents_x {
+                if let Some(adj_v) = self.vertices.get_mut(&v) {
+                    adj_v.remove(&x);
+                }
+            }
+            // At this point, no other vertices should point to x, and so x can
+            // be removed. Every vertex has an entry in both maps, so it is safe
+            // to unwrap.
+            let adj_x = self.vertices.remove(&x).unwrap();
+
+            // Remove x from the reverse adjacency list of each of its children.
+            for y in &adj_x {
+                if let Some(parents_y) = self.parents.get_mut(y) {
+                    parents_y.remove(&x);
+                }
+            }
+            return Ok(adj_x);
+        }
+        Err(Error::VertexDoesNotExist)
+    }
+}
+
+impl<T> SplitEdge<T> for BTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    type Error = Error;
+    fn split_edge(&mut self, x: T, y: T, z: T) -> Result<(), Self::Error> {
+        // Validate everything before changing anything.
+        match (self.vertices.get(&x), self.vertices.contains_key(&y)) {
+            (Some(adj_x), true) => {
+                if !adj_x.contains(&y) {
+                    return Err(Error::EdgeDoesNotExist);
+                }
+            }
+            _ => return Err(Error::VertexDoesNotExist),
+        }
+        if self.vertices.contains_key(&z) {
+            return Err(Error::VertexExists);
+        }
+
+        self.remove_edge(x.clone(), y.clone())?;
+        self.add_vertex(z.clone());
+        self.insert_edge(x, z.clone());
+        self.insert_edge(z, y);
+        Ok(())
+    }
+}
+
+impl<T> MergeVertices<T> for BTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    type Error = EdgeError<T>;
+    fn merge_vertices(&mut self, a: T, b: T, into: T) -> Result<(), Self::Error> {
+        let (parents, children) = self.merge_edges(&a, &b, &into)?;
+        // Both exist, so neither removal can fail, but b is already gone if it
+        // is a.
+        let _ = self.remove_vertex(a);
+        let _ = self.remove_vertex(b);
+        self.add_vertex(into.clone());
+        for u in parents {
+            self.insert_edge(u, into.clone());
+        }
+        for w in children {
+            self.insert_edge(into.clone(), w);
+        }
+        Ok(())
+    }
+}
+
+impl<T> RemoveVertexBypass<T> for BTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    type Error = Error;
+    fn remove_vertex_bypass(&mut self, x: T) -> Result<BTreeSet<(T, T)>, Self::Error> {
+        let parents_x: BTreeSet<T> = self
+            .parents
+            .get(&x)
+            .ok_or(Error::VertexDoesNotExist)?
+            .clone();
+        let adj_x = self.remove_vertex(x)?;
 
-        // We can be sure that if there has not been an error thrown by now,
-        // then x definitely exists in then vertices, so it is safe to unwrap.
-        Ok(self.vertices.remove(&x).unwrap())
+        // Any path through a bypass edge (u, w) was already a path through x,
+        // so the edges are added without checking for cycles.
+        let mut added: BTreeSet<(T, T)> = BTreeSet::new();
+        for u in &parents_x {
+            for w in &adj_x {
+                if !self.vertices[u].contains(w) {
+                    self.insert_edge(u.clone(), w.clone());
+                    added.insert((u.clone(), w.clone()));
+                }
+            }
+        }
+        Ok(added)
     }
 }
 
@@ -162,7 +486,7 @@ where
 {
     type Error = Error;
     fn adjacent(&self, x: T, y: T) -> Result<bool, Self::Error> {
-        if self.vertices.get(&y).is_some() {
+        if self.vertices.contains_key(&y) {
             if let Some(adj_x) = self.vertices.get(&x) {
                 if adj_x.contains(&y) {
                     return Ok(true);
@@ -183,13 +507,582 @@ where
     }
 }
 
-impl<T> Prune<T> for BTreeDAG<T> where T: Ord + Clone {
+impl<T> Prune<T> for BTreeDAG<T>
+where
+    T: Ord + Clone,
+{
     type Error = Error;
-    fn prune(&mut self, x: T) -> Result<(), Self::Error> {
-        let child_vertices = self.remove_vertex(x)?;
-        for vertex in child_vertices {
-            self.prune(vertex)?;
+    fn prune(&mut self, x: T) -> Result<BTreeSet<T>, Self::Error> {
+        self.prune_with(x, PruneMode::Cascade)
+    }
+
+    fn prune_with(&mut self, x: T, mode: PruneMode) -> Result<BTreeSet<T>, Self::Error> {
+        let removed: BTreeSet<T> = self
+            .prune_set(&x, mode)
+            .ok_or(Error::VertexDoesNotExist)?
+            .into_iter()
+            .cloned()
+            .collect();
+        for v in &removed {
+            // Every vertex in the set exists, and is only removed once.
+            self.remove_vertex(v.clone())?;
         }
-        Ok(())
+        Ok(removed)
+    }
+}
+
+impl<T> TopologicalSort<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    fn topological_sort(&self) -> Vec<&T> {
+        self.topological_iter().collect()
+    }
+
+    fn topological_iter(&self) -> TopologicalIter<'_, T> {
+        TopologicalIter::new(self)
+    }
+}
+
+impl<T> Generations<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    fn generations(&self) -> Vec<BTreeSet<&T>> {
+        let mut in_degrees = self.in_degrees();
+        let mut generation: BTreeSet<&T> = in_degrees
+            .iter()
+            .filter(|(_, in_degree)| **in_degree == 0)
+            .map(|(v, _)| *v)
+            .collect();
+        let mut generations: Vec<BTreeSet<&T>> = Vec::new();
+        while !generation.is_empty() {
+            // A vertex joins the next generation once every one of
+            // its parents belongs to an earlier generation.
+            let mut next_generation: BTreeSet<&T> = BTreeSet::new();
+            for x in &generation {
+                for y in &self.vertices[*x] {
+                    if let Some(in_degree) = in_degrees.get_mut(y) {
+                        *in_degree -= 1;
+                        if *in_degree == 0 {
+                            next_generation.insert(y);
+                        }
+                    }
+                }
+            }
+            generations.push(generation);
+            generation = next_generation;
+        }
+        generations
+    }
+}
+
+impl<T> Parents<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    fn parents(&self, y: T) -> Option<&BTreeSet<T>> {
+        self.parents.get(&y)
+    }
+}
+
+impl<T> InDegree<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    fn in_degree(&self, x: T) -> Option<usize> {
+        self.parents.get(&x).map(|parents_x| parents_x.len())
+    }
+}
+
+impl<T> OutDegree<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    fn out_degree(&self, x: T) -> Option<usize> {
+        self.vertices.get(&x).map(|adj_x| adj_x.len())
+    }
+}
+
+impl<T> Roots<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    fn roots(&self) -> BTreeSet<&T> {
+        self.parents
+            .iter()
+            .filter(|(_, parents_v)| parents_v.is_empty())
+            .map(|(v, _)| v)
+            .collect()
+    }
+}
+
+impl<T> Leaves<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    fn leaves(&self) -> BTreeSet<&T> {
+        self.vertices
+            .iter()
+            .filter(|(_, adj_v)| adj_v.is_empty())
+            .map(|(v, _)| v)
+            .collect()
+    }
+}
+
+impl<T> Ancestors<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    fn ancestors(&self, x: T) -> Option<BTreeSet<&T>> {
+        self.ancestors_iter(x, Traversal::BreadthFirst)
+            .map(|iter| iter.collect())
+    }
+
+    fn ancestors_iter(&self, x: T, traversal: Traversal) -> Option<TraversalIter<'_, T>> {
+        let (x, _) = self.parents.get_key_value(&x)?;
+        Some(TraversalIter::new(&self.parents, x, traversal))
+    }
+}
+
+impl<T> Descendants<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    fn descendants(&self, x: T) -> Option<BTreeSet<&T>> {
+        self.descendants_iter(x, Traversal::BreadthFirst)
+            .map(|iter| iter.collect())
+    }
+
+    fn descendants_iter(&self, x: T, traversal: Traversal) -> Option<TraversalIter<'_, T>> {
+        let (x, _) = self.vertices.get_key_value(&x)?;
+        Some(TraversalIter::new(&self.vertices, x, traversal))
+    }
+}
+
+impl<T> Reachable<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    type Error = Error;
+    fn has_path(&self, x: T, y: T) -> Result<bool, Self::Error> {
+        if self.vertices.contains_key(&y) && self.vertices.contains_key(&x) {
+            return Ok(self.find_path(&x, &y).is_some());
+        }
+        Err(Error::VertexDoesNotExist)
+    }
+}
+
+impl<T> WouldCreateCycle<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    type Error = Error;
+    fn would_create_cycle(&self, x: &T, y: &T) -> Result<bool, Self::Error> {
+        if self.vertices.contains_key(x) && self.vertices.contains_key(y) {
+            // This is the same search `add_edge` runs before inserting (x, y).
+            return Ok(self.find_path(y, x).is_some());
+        }
+        Err(Error::VertexDoesNotExist)
+    }
+}
+
+impl<T> TransitiveReduction<T> for BTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    fn redundant_edges(&self) -> BTreeSet<(&T, &T)> {
+        let mut redundant_edges: BTreeSet<(&T, &T)> = BTreeSet::new();
+        for (x, adj_x) in &self.vertices {
+            // The edge (x, y) is redundant if, and only if, y can also be
+            // reached from x by a path of two or more edges, i.e. from a
+            // grandchild of x.
+            let grandchildren = adj_x.iter().flat_map(|y| &self.vertices[y]);
+            let reachable = Self::reach(&self.vertices, grandchildren);
+            for y in adj_x {
+                if reachable.contains(y) {
+                    redundant_edges.insert((x, y));
+                }
+            }
+        }
+        redundant_edges
+    }
+
+    fn transitive_reduction(&self) -> BTreeDAG<T> {
+        let mut dag = self.clone();
+        dag.reduce();
+        dag
+    }
+
+    fn reduce(&mut self) -> BTreeSet<(T, T)> {
+        let redundant_edges: BTreeSet<(T, T)> = self
+            .redundant_edges()
+            .into_iter()
+            .map(|(x, y)| (x.clone(), y.clone()))
+            .collect();
+        // Removing an edge which is redundant in the original dag never makes
+        // another redundant edge necessary, so they can all be removed at once.
+        for (x, y) in &redundant_edges {
+            self.vertices.get_mut(x).unwrap().remove(y);
+            self.parents.get_mut(y).unwrap().remove(x);
+        }
+        redundant_edges
+    }
+}
+
+impl<T> TransitiveClosure<T> for BTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    fn transitive_closure(&self) -> BTreeDAG<T> {
+        let mut vertices: BTreeMap<T, BTreeSet<T>> = BTreeMap::new();
+        // In reverse topological order, the closure of every child of x is
+        // complete by the time x is reached.
+        let order = self.topological_sort();
+        for x in order.into_iter().rev() {
+            let mut closure_x: BTreeSet<T> = BTreeSet::new();
+            for y in &self.vertices[x] {
+                closure_x.insert(y.clone());
+                closure_x.extend(vertices[y].iter().cloned());
+            }
+            vertices.insert(x.clone(), closure_x);
+        }
+        let mut parents: BTreeMap<T, BTreeSet<T>> = vertices
+            .keys()
+            .map(|v| (v.clone(), BTreeSet::new()))
+            .collect();
+        for (x, closure_x) in &vertices {
+            for y in closure_x {
+                parents.get_mut(y).unwrap().insert(x.clone());
+            }
+        }
+        BTreeDAG { vertices, parents }
+    }
+}
+
+impl<T> CriticalPath<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    fn critical_path<C, F>(&self, vertex_cost: F) -> Option<(Vec<&T>, C)>
+    where
+        C: Copy + Ord + Add<Output = C> + Default,
+        F: Fn(&T) -> C,
+    {
+        self.critical_path_with_edges(vertex_cost, |_, _| C::default())
+    }
+
+    fn critical_path_with_edges<C, F, G>(
+        &self,
+        vertex_cost: F,
+        edge_cost: G,
+    ) -> Option<(Vec<&T>, C)>
+    where
+        C: Copy + Ord + Add<Output = C> + Default,
+        F: Fn(&T) -> C,
+        G: Fn(&T, &T) -> C,
+    {
+        // For every vertex, the cost of the most expensive path ending at it,
+        // and the parent that path arrives from, if any. With negative costs,
+        // starting the path at the vertex itself may be more expensive than
+        // arriving from any parent.
+        let mut costs: BTreeMap<&T, (C, Option<&T>)> = BTreeMap::new();
+        let mut end: Option<(&T, C)> = None;
+        for y in self.topological_iter() {
+            let mut cost_y: (C, Option<&T>) = (C::default(), None);
+            for x in &self.parents[y] {
+                let cost = costs[x].0 + edge_cost(x, y);
+                if cost > cost_y.0 {
+                    cost_y = (cost, Some(x));
+                }
+            }
+            cost_y.0 = cost_y.0 + vertex_cost(y);
+            if end.map_or(true, |(_, cost)| cost_y.0 > cost) {
+                end = Some((y, cost_y.0));
+            }
+            costs.insert(y, cost_y);
+        }
+        let (y, cost) = end?;
+        let mut path: Vec<&T> = Vec::new();
+        let mut v = Some(y);
+        while let Some(u) = v {
+            path.push(u);
+            v = costs[u].1;
+        }
+        path.reverse();
+        Some((path, cost))
+    }
+}
+
+impl<T> ScheduleAnalysis<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    fn schedule<C, F>(&self, duration: F) -> Schedule<'_, T, C>
+    where
+        C: Copy + Ord + Add<Output = C> + Sub<Output = C> + Default,
+        F: Fn(&T) -> C,
+    {
+        let order: Vec<&T> = self.topological_sort();
+        let mut durations: BTreeMap<&T, C> = BTreeMap::new();
+        let mut earliest_finishes: BTreeMap<&T, C> = BTreeMap::new();
+        let mut makespan: C = C::default();
+
+        // Forward pass: a vertex starts once its last parent finishes.
+        for y in &order {
+            let duration_y = duration(y);
+            let earliest_start = self.parents[*y]
+                .iter()
+                .map(|x| earliest_finishes[x])
+                .max()
+                .unwrap_or_default();
+            let earliest_finish = earliest_start + duration_y;
+            if earliest_finish > makespan {
+                makespan = earliest_finish;
+            }
+            durations.insert(y, duration_y);
+            earliest_finishes.insert(y, earliest_finish);
+        }
+
+        // Backward pass: a vertex must finish before its first child starts.
+        let mut timings: BTreeMap<&T, Timing<C>> = BTreeMap::new();
+        for x in order.into_iter().rev() {
+            let latest_finish = self.vertices[x]
+                .iter()
+                .map(|y| timings[y].latest_start)
+                .min()
+                .unwrap_or(makespan);
+            let latest_start = latest_finish - durations[x];
+            let earliest_finish = earliest_finishes[x];
+            let earliest_start = earliest_finish - durations[x];
+            timings.insert(
+                x,
+                Timing {
+                    earliest_start,
+                    earliest_finish,
+                    latest_start,
+                    latest_finish,
+                    slack: latest_start - earliest_start,
+                },
+            );
+        }
+        Schedule { timings, makespan }
+    }
+}
+
+impl<T> ShortestPath<T> for BTreeDAG<T>
+where
+    T: Ord + Clone,
+{
+    type Error = Error;
+    fn shortest_path(&self, x: T, y: T) -> Result<Option<Vec<T>>, Self::Error> {
+        if !self.vertices.contains_key(&y) {
+            return Err(Error::VertexDoesNotExist);
+        }
+        let (x, _) = self
+            .vertices
+            .get_key_value(&x)
+            .ok_or(Error::VertexDoesNotExist)?;
+        // Breadth first search reaches every vertex by a path with the fewest
+        // edges first, recording the vertex it was discovered from.
+        let mut predecessors: BTreeMap<&T, &T> = BTreeMap::new();
+        let mut visited: BTreeSet<&T> = BTreeSet::new();
+        let mut queue: VecDeque<&T> = VecDeque::new();
+        visited.insert(x);
+        queue.push_back(x);
+        while let Some(v) = queue.pop_front() {
+            if *v == y {
+                let mut path: Vec<T> = Vec::new();
+                path.push(v.clone());
+                let mut v = v;
+                while let Some(u) = predecessors.get(v) {
+                    path.push((*u).clone());
+                    v = u;
+                }
+                path.reverse();
+                return Ok(Some(path));
+            }
+            for w in &self.vertices[v] {
+                if visited.insert(w) {
+                    predecessors.insert(w, v);
+                    queue.push_back(w);
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<T> AllPaths<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    type Error = Error;
+    fn paths(&self, x: T, y: T) -> Result<PathsIter<'_, T>, Self::Error> {
+        match (
+            self.vertices.get_key_value(&x),
+            self.parents.get_key_value(&y),
+        ) {
+            (Some((x, _)), Some((y, _))) => {
+                let useful = Self::reach(&self.parents, Some(y));
+                Ok(PathsIter::new(&self.vertices, x, y, useful))
+            }
+            _ => Err(Error::VertexDoesNotExist),
+        }
+    }
+
+    fn count_paths(&self, x: T, y: T) -> Result<Option<u64>, Self::Error> {
+        if !self.vertices.contains_key(&x) {
+            return Err(Error::VertexDoesNotExist);
+        }
+        let (y, _) = self
+            .parents
+            .get_key_value(&y)
+            .ok_or(Error::VertexDoesNotExist)?;
+        // Only vertices from which y can be reached are counted, so the count
+        // of any of them overflowing implies the count of y overflows too.
+        let useful = Self::reach(&self.parents, Some(y));
+        let mut counts: BTreeMap<&T, u64> = BTreeMap::new();
+        for v in self.topological_iter().filter(|v| useful.contains(v)) {
+            let count = if *v == x {
+                1
+            } else {
+                let mut count: u64 = 0;
+                for u in &self.parents[v] {
+                    if let Some(count_u) = counts.get(u) {
+                        match count.checked_add(*count_u) {
+                            Some(sum) => count = sum,
+                            None => return Ok(None),
+                        }
+                    }
+                }
+                count
+            };
+            if count > 0 {
+                counts.insert(v, count);
+            }
+        }
+        Ok(Some(counts.get(y).copied().unwrap_or(0)))
+    }
+}
+
+impl<T> CommonAncestors<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    type Error = Error;
+    fn common_ancestors(&self, a: T, b: T) -> Result<BTreeSet<&T>, Self::Error> {
+        self.common_ancestors_of(vec![a, b])
+    }
+
+    fn lowest_common_ancestors(&self, a: T, b: T) -> Result<BTreeSet<&T>, Self::Error> {
+        self.lowest_common_ancestors_of(vec![a, b])
+    }
+
+    fn common_ancestors_of<I>(&self, vertices: I) -> Result<BTreeSet<&T>, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut common: Option<BTreeSet<&T>> = None;
+        for x in vertices {
+            let (x, _) = self
+                .parents
+                .get_key_value(&x)
+                .ok_or(Error::VertexDoesNotExist)?;
+            // Every vertex counts as one of its own ancestors.
+            let ancestors_x = Self::reach(&self.parents, Some(x));
+            common = Some(match common {
+                Some(common) => common.intersection(&ancestors_x).copied().collect(),
+                None => ancestors_x,
+            });
+        }
+        Ok(common.unwrap_or_default())
+    }
+
+    fn lowest_common_ancestors_of<I>(&self, vertices: I) -> Result<BTreeSet<&T>, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let common = self.common_ancestors_of(vertices)?;
+        // A common ancestor is not lowest if it is a proper ancestor of
+        // another common ancestor.
+        let grandparents = common.iter().flat_map(|v| &self.parents[*v]);
+        let dominated = Self::reach(&self.parents, grandparents);
+        Ok(common.difference(&dominated).copied().collect())
+    }
+}
+
+impl<T> Dominators<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    fn dominator_tree(&self, root: T) -> Option<DominatorTree<'_, T>> {
+        let (root, _) = self.vertices.get_key_value(&root)?;
+        Some(DominatorTree::new(&self.vertices, &self.parents, root))
+    }
+
+    fn post_dominator_tree(&self, sink: T) -> Option<DominatorTree<'_, T>> {
+        let (sink, _) = self.parents.get_key_value(&sink)?;
+        Some(DominatorTree::new(&self.parents, &self.vertices, sink))
+    }
+}
+
+impl<T> ImpactAnalysis<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    type Error = Error;
+    fn affected<I>(&self, changed: I) -> Result<Vec<&T>, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut seeds: Vec<&T> = Vec::new();
+        for x in changed {
+            let (x, _) = self
+                .vertices
+                .get_key_value(&x)
+                .ok_or(Error::VertexDoesNotExist)?;
+            seeds.push(x);
+        }
+        let region = Self::reach(&self.vertices, seeds);
+        Ok(TopologicalIter::within(self, &region).collect())
+    }
+
+    fn affected_within<I, J>(&self, changed: I, interesting: J) -> Result<Vec<&T>, Self::Error>
+    where
+        I: IntoIterator<Item = T>,
+        J: IntoIterator<Item = T>,
+    {
+        let mut filter: BTreeSet<T> = BTreeSet::new();
+        for x in interesting {
+            if !self.vertices.contains_key(&x) {
+                return Err(Error::VertexDoesNotExist);
+            }
+            filter.insert(x);
+        }
+        let mut affected = self.affected(changed)?;
+        affected.retain(|v| filter.contains(*v));
+        Ok(affected)
+    }
+}
+
+impl<T> PreviewRemoval<T> for BTreeDAG<T>
+where
+    T: Ord,
+{
+    type Error = Error;
+    fn remove_vertex_preview(&self, x: T) -> Result<Removal<'_, T>, Self::Error> {
+        let (x, _) = self
+            .vertices
+            .get_key_value(&x)
+            .ok_or(Error::VertexDoesNotExist)?;
+        let mut vertices: BTreeSet<&T> = BTreeSet::new();
+        vertices.insert(x);
+        Ok(self.removal(vertices))
+    }
+
+    fn prune_preview(&self, x: T, mode: PruneMode) -> Result<Removal<'_, T>, Self::Error> {
+        let vertices = self.prune_set(&x, mode).ok_or(Error::VertexDoesNotExist)?;
+        Ok(self.removal(vertices))
     }
 }
@@ -0,0 +1,13 @@
+use alloc::collections::BTreeSet;
+
+/// `Removal` is the result of `PreviewRemoval`: the vertices which would be removed,
+/// and every edge which would be removed along with them, i.e. every edge into or out
+/// of any of those vertices. Edges are (x, y) pairs, for the edge from x to y.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Removal<'a, T>
+where
+    T: Ord,
+{
+    pub vertices: BTreeSet<&'a T>,
+    pub edges: BTreeSet<(&'a T, &'a T)>,
+}
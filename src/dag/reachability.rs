@@ -0,0 +1,217 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::dag::*;
+use crate::Error;
+
+/// Dags with at most this many vertices are indexed with a bitset closure by
+/// `ReachabilityIndex::new`, which costs V * V bits. Larger dags use interval labels.
+pub const BITSET_THRESHOLD: usize = 4096;
+
+/// `ReachabilityIndex` is a precomputed, read only index over a snapshot of a `BTreeDAG`
+/// which answers whether there is a path from the vertex x to the vertex y without
+/// traversing the dag. The index does not follow later changes to the dag; rebuild it
+/// whenever the dag changes.
+///
+/// Two labelings are available. `bitset` stores the full transitive closure as one bit
+/// per pair of vertices, and answers in O(1). `intervals` numbers the vertices in post
+/// order along a spanning forest, and stores for every vertex the merged intervals of
+/// post order numbers it reaches, answering with a binary search. `new` picks between
+/// them using `BITSET_THRESHOLD`.
+///
+/// # Example
+///
+/// ```
+/// use btree_dag::{BTreeDAG, AddVertex, AddEdge, ReachabilityIndex};
+/// let mut dag: BTreeDAG<String> = BTreeDAG::new();
+/// dag.add_vertex(String::from("origin"));
+/// dag.add_vertex(String::from("waypoint"));
+/// dag.add_vertex(String::from("destination"));
+/// dag.add_edge(String::from("origin"), String::from("waypoint"));
+/// dag.add_edge(String::from("waypoint"), String::from("destination"));
+///
+/// let index: ReachabilityIndex<String> = ReachabilityIndex::new(&dag);
+/// let origin = String::from("origin");
+/// let destination = String::from("destination");
+/// assert!(index.reaches(&origin, &destination).unwrap());
+/// assert!(!index.reaches(&destination, &origin).unwrap());
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ReachabilityIndex<T>
+where
+    T: Ord,
+{
+    indices: BTreeMap<T, usize>,
+    labels: Labels,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+enum Labels {
+    /// Row i, of `words` words, has bit j set if vertex i reaches vertex j.
+    Bitset { words: usize, rows: Vec<u64> },
+    /// Vertex i reaches vertex j if `post[j]` lies within one of the sorted,
+    /// disjoint, inclusive `intervals[i]`.
+    Intervals {
+        post: Vec<usize>,
+        intervals: Vec<Vec<(usize, usize)>>,
+    },
+}
+
+impl<T> ReachabilityIndex<T>
+where
+    T: Ord + Clone,
+{
+    /// Builds a bitset index for dags with at most `BITSET_THRESHOLD` vertices,
+    /// and an interval index otherwise.
+    pub fn new(dag: &BTreeDAG<T>) -> Self {
+        if dag.vertices.len() <= BITSET_THRESHOLD {
+            Self::bitset(dag)
+        } else {
+            Self::intervals(dag)
+        }
+    }
+
+    pub fn bitset(dag: &BTreeDAG<T>) -> Self {
+        let (indices, children) = Self::index(dag);
+        let n = children.len();
+        let words = n.div_ceil(64);
+        let mut rows: Vec<u64> = vec![0; n * words];
+        // Vertices are indexed in topological order, so every child's row is
+        // complete before its parents are visited in reverse.
+        for i in (0..n).rev() {
+            rows[i * words + i / 64] |= 1 << (i % 64);
+            for &j in &children[i] {
+                for w in 0..words {
+                    rows[i * words + w] |= rows[j * words + w];
+                }
+            }
+        }
+        ReachabilityIndex {
+            indices,
+            labels: Labels::Bitset { words, rows },
+        }
+    }
+
+    pub fn intervals(dag: &BTreeDAG<T>) -> Self {
+        let (indices, children) = Self::index(dag);
+        let n = children.len();
+
+        // Number the vertices in post order along a depth first spanning
+        // forest, recording the least number in each vertex's subtree.
+        let mut post: Vec<usize> = vec![usize::MAX; n];
+        let mut low: Vec<usize> = vec![0; n];
+        let mut counter: usize = 0;
+        let mut stack: Vec<(usize, usize)> = Vec::new();
+        for root in 0..n {
+            if post[root] != usize::MAX {
+                continue;
+            }
+            low[root] = counter;
+            // Mark the root as discovered until it receives its number.
+            post[root] = usize::MAX - 1;
+            stack.push((root, 0));
+            while let Some((v, next_child)) = stack.pop() {
+                if let Some(&w) = children[v].get(next_child) {
+                    stack.push((v, next_child + 1));
+                    if post[w] == usize::MAX {
+                        low[w] = counter;
+                        post[w] = usize::MAX - 1;
+                        stack.push((w, 0));
+                    }
+                } else {
+                    post[v] = counter;
+                    counter += 1;
+                }
+            }
+        }
+
+        // In reverse topological order, a vertex reaches its own subtree and
+        // everything its children reach.
+        let mut intervals: Vec<Vec<(usize, usize)>> = vec![Vec::new(); n];
+        for i in (0..n).rev() {
+            let mut intervals_i: Vec<(usize, usize)> = vec![(low[i], post[i])];
+            for &j in &children[i] {
+                intervals_i.extend(intervals[j].iter().copied());
+            }
+            intervals[i] = merge(intervals_i);
+        }
+        ReachabilityIndex {
+            indices,
+            labels: Labels::Intervals { post, intervals },
+        }
+    }
+
+    /// Indexes the vertices in topological order, and lists each vertex's
+    /// children by index.
+    fn index(dag: &BTreeDAG<T>) -> (BTreeMap<T, usize>, Vec<Vec<usize>>) {
+        let indices: BTreeMap<T, usize> = dag
+            .topological_iter()
+            .enumerate()
+            .map(|(i, v)| (v.clone(), i))
+            .collect();
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); indices.len()];
+        for (v, i) in &indices {
+            children[*i] = dag.vertices[v].iter().map(|w| indices[w]).collect();
+        }
+        (indices, children)
+    }
+}
+
+impl<T> ReachabilityIndex<T>
+where
+    T: Ord,
+{
+    /// Tests whether there is a path from the vertex x to the vertex y. Every
+    /// vertex reaches itself. An error is thrown if either x, or y do not exist.
+    pub fn reaches(&self, x: &T, y: &T) -> Result<bool, Error> {
+        match (self.indices.get(x), self.indices.get(y)) {
+            (Some(&i), Some(&j)) => Ok(match &self.labels {
+                Labels::Bitset { words, rows } => rows[i * words + j / 64] & (1 << (j % 64)) != 0,
+                Labels::Intervals { post, intervals } => {
+                    let p = post[j];
+                    // Find the last interval starting at or before p.
+                    let k = intervals[i].partition_point(|(start, _)| *start <= p);
+                    k > 0 && intervals[i][k - 1].1 >= p
+                }
+            }),
+            _ => Err(Error::VertexDoesNotExist),
+        }
+    }
+
+    /// Returns the number of indexed vertices.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+impl<T> Reachable<T> for ReachabilityIndex<T>
+where
+    T: Ord,
+{
+    type Error = Error;
+    fn has_path(&self, x: T, y: T) -> Result<bool, Self::Error> {
+        self.reaches(&x, &y)
+    }
+}
+
+/// Sorts the inclusive intervals and merges those which overlap or touch.
+fn merge(mut intervals: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    intervals.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(intervals.len());
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => {
+                if end > last.1 {
+                    last.1 = end;
+                }
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
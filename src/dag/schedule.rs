@@ -0,0 +1,54 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+
+/// `Timing` holds the PERT schedule of a single vertex. A vertex may start as soon
+/// as all of its parents have finished, and its slack is how long its start may be
+/// delayed without delaying the schedule as a whole.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Timing<C> {
+    pub earliest_start: C,
+    pub earliest_finish: C,
+    pub latest_start: C,
+    pub latest_finish: C,
+    pub slack: C,
+}
+
+/// `Schedule` is the result of `ScheduleAnalysis::schedule`: the `Timing` of every
+/// vertex, and the makespan, i.e. the earliest time at which every vertex can have
+/// finished.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Schedule<'a, T, C>
+where
+    T: Ord,
+{
+    pub(crate) timings: BTreeMap<&'a T, Timing<C>>,
+    pub(crate) makespan: C,
+}
+
+impl<'a, T, C> Schedule<'a, T, C>
+where
+    T: Ord,
+    C: Copy + Ord + Default,
+{
+    /// Returns the timing of the vertex x, or `None` if x does not exist.
+    pub fn timing(&self, x: &T) -> Option<&Timing<C>> {
+        self.timings.get(x)
+    }
+
+    pub fn timings(&self) -> &BTreeMap<&'a T, Timing<C>> {
+        &self.timings
+    }
+
+    pub fn makespan(&self) -> C {
+        self.makespan
+    }
+
+    /// Returns the vertices without slack. Delaying any of them delays the
+    /// schedule as a whole.
+    pub fn critical_vertices(&self) -> BTreeSet<&'a T> {
+        self.timings
+            .iter()
+            .filter(|(_, timing)| timing.slack == C::default())
+            .map(|(v, _)| *v)
+            .collect()
+    }
+}
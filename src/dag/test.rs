@@ -2,8 +2,10 @@
 
 mod unit_tests {
     use crate::dag::*;
-    use crate::Error;
+    use crate::{EdgeError, Error};
     use alloc::collections::{BTreeMap, BTreeSet};
+    use alloc::vec;
+    use alloc::vec::Vec;
 
     #[test]
     fn test_dag() {
@@ -16,9 +18,10 @@ mod unit_tests {
         // Instantiate a dag using the new associated function.
         let dag: BTreeDAG<usize> = BTreeDAG::new();
         let vertices: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+        let parents: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
 
         // Check dag struct is generated.
-        assert_eq!(dag, BTreeDAG { vertices })
+        assert_eq!(dag, BTreeDAG { vertices, parents })
 
         // Test passed
     }
@@ -93,12 +96,28 @@ mod unit_tests {
 
         // If you attempt to add an edge to a vertex that does not
         // exist, then an error is raised.
-        assert_eq!(dag.add_edge(0, 3).unwrap_err(), Error::VertexDoesNotExist);
-        assert_eq!(dag.add_edge(3, 0).unwrap_err(), Error::VertexDoesNotExist);
-        assert_eq!(dag.add_edge(1, 3).unwrap_err(), Error::VertexDoesNotExist);
-        assert_eq!(dag.add_edge(3, 1).unwrap_err(), Error::VertexDoesNotExist);
-
-        assert_eq!(dag.add_edge(2, 1).unwrap_err(), Error::EdgeExists);
+        assert_eq!(
+            dag.add_edge(0, 3).unwrap_err(),
+            EdgeError::VertexDoesNotExist
+        );
+        assert_eq!(
+            dag.add_edge(3, 0).unwrap_err(),
+            EdgeError::VertexDoesNotExist
+        );
+        assert_eq!(
+            dag.add_edge(1, 3).unwrap_err(),
+            EdgeError::VertexDoesNotExist
+        );
+        assert_eq!(
+            dag.add_edge(3, 1).unwrap_err(),
+            EdgeError::VertexDoesNotExist
+        );
+
+        // The edge (2, 1) would close the path 1 -> 2 into a cycle.
+        assert_eq!(
+            dag.add_edge(2, 1).unwrap_err(),
+            EdgeError::Cycle(vec![1, 2])
+        );
 
         assert!(dag.add_edge(2, 1).is_err());
 
@@ -110,7 +129,16 @@ mod unit_tests {
         dag.add_edge(3, 4)?;
         dag.add_edge(4, 5)?;
 
-        assert!(dag.add_edge(5, 1).is_err());
+        assert_eq!(
+            dag.add_edge(5, 1).unwrap_err(),
+            EdgeError::Cycle(vec![1, 2, 3, 4, 5])
+        );
+
+        // A rejected edge converts into `Error::EdgeExists`.
+        assert_eq!(
+            Error::from(dag.add_edge(5, 1).unwrap_err()),
+            Error::EdgeExists
+        );
 
         // Tests passed.
         Ok(())
@@ -362,5 +390,1553 @@ mod unit_tests {
         Ok(())
     }
 
+    #[test]
+    fn topological_sort() -> Result<(), Error> {
+        // Add six nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        dag.add_vertex(0);
+        dag.add_vertex(1);
+        dag.add_vertex(2);
+        dag.add_vertex(3);
+        dag.add_vertex(4);
+        dag.add_vertex(5);
+
+        // Add edges (5, 0), (4, 0), (5, 2), (2, 3), (3, 1), and (4, 1).
+        dag.add_edge(5, 0)?;
+        dag.add_edge(4, 0)?;
+        dag.add_edge(5, 2)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(3, 1)?;
+        dag.add_edge(4, 1)?;
+
+        // Ties are broken by the ordering of the vertices.
+        assert_eq!(dag.topological_sort(), vec![&4, &5, &0, &2, &3, &1]);
+
+        // The lazy variant yields the same ordering.
+        let mut iter = dag.topological_iter();
+        assert_eq!(iter.next(), Some(&4));
+        assert_eq!(iter.next(), Some(&5));
+        assert_eq!(iter.collect::<Vec<&usize>>(), vec![&0, &2, &3, &1]);
+
+        // Every edge points forward in the ordering.
+        let order = dag.topological_sort();
+        for (i, x) in order.iter().enumerate() {
+            for y in dag.connections(**x).unwrap() {
+                assert!(order[i..].contains(&y));
+            }
+        }
+
+        // An empty dag has an empty ordering.
+        let dag: BTreeDAG<usize> = BTreeDAG::new();
+        assert!(dag.topological_sort().is_empty());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn generations() -> Result<(), Error> {
+        // Add six nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        dag.add_vertex(0);
+        dag.add_vertex(1);
+        dag.add_vertex(2);
+        dag.add_vertex(3);
+        dag.add_vertex(4);
+        dag.add_vertex(5);
+
+        // Add edges (0, 1), (0, 2), (1, 3), (2, 3), and (0, 3).
+        dag.add_edge(0, 1)?;
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 3)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(0, 3)?;
+
+        // Vertices 0, 4, and 5 have no parents; 1, and 2 depend only on 0;
+        // and 3 depends on 1, and 2.
+        let generations = dag.generations();
+        assert_eq!(generations.len(), 3);
+
+        let mut exp_generation_0: BTreeSet<&usize> = BTreeSet::new();
+        exp_generation_0.insert(&0);
+        exp_generation_0.insert(&4);
+        exp_generation_0.insert(&5);
+        assert_eq!(generations[0], exp_generation_0);
+
+        let mut exp_generation_1: BTreeSet<&usize> = BTreeSet::new();
+        exp_generation_1.insert(&1);
+        exp_generation_1.insert(&2);
+        assert_eq!(generations[1], exp_generation_1);
+
+        let mut exp_generation_2: BTreeSet<&usize> = BTreeSet::new();
+        exp_generation_2.insert(&3);
+        assert_eq!(generations[2], exp_generation_2);
+
+        // An empty dag has no generations.
+        let dag: BTreeDAG<usize> = BTreeDAG::new();
+        assert!(dag.generations().is_empty());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn parents() -> Result<(), Error> {
+        // Add four nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        dag.add_vertex(0);
+        dag.add_vertex(1);
+        dag.add_vertex(2);
+        dag.add_vertex(3);
+
+        // Add an edge (0, 2), (1, 2), and (2, 3).
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 2)?;
+        dag.add_edge(2, 3)?;
+
+        // Vertices 0, and 1 point at vertex 2.
+        let mut exp_parents_2: BTreeSet<usize> = BTreeSet::new();
+        exp_parents_2.insert(0);
+        exp_parents_2.insert(1);
+        assert_eq!(dag.parents(2).unwrap(), &exp_parents_2);
+        assert!(dag.parents(0).unwrap().is_empty());
+
+        // Removing an edge also updates the parents of y.
+        dag.remove_edge(1, 2)?;
+        let mut exp_parents_2: BTreeSet<usize> = BTreeSet::new();
+        exp_parents_2.insert(0);
+        assert_eq!(dag.parents(2).unwrap(), &exp_parents_2);
+
+        // Removing a vertex removes it from the parents of its children.
+        dag.remove_vertex(2)?;
+        assert!(dag.parents(3).unwrap().is_empty());
+        assert!(dag.connections(0).unwrap().is_empty());
+
+        // Re-adding a vertex clears its edges in both directions.
+        dag.add_edge(0, 3)?;
+        dag.add_vertex(0);
+        assert!(dag.parents(3).unwrap().is_empty());
+
+        // If we try to check parents on a node that does not exist,
+        // None is returned.
+        assert!(dag.parents(2).is_none());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn in_degree_and_out_degree() -> Result<(), Error> {
+        // Add three nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        dag.add_vertex(0);
+        dag.add_vertex(1);
+        dag.add_vertex(2);
+
+        // Add an edge (0, 1), (1, 2), and (0, 2).
+        dag.add_edge(0, 1)?;
+        dag.add_edge(1, 2)?;
+        dag.add_edge(0, 2)?;
+
+        assert_eq!(dag.in_degree(0), Some(0));
+        assert_eq!(dag.in_degree(1), Some(1));
+        assert_eq!(dag.in_degree(2), Some(2));
+
+        assert_eq!(dag.out_degree(0), Some(2));
+        assert_eq!(dag.out_degree(1), Some(1));
+        assert_eq!(dag.out_degree(2), Some(0));
+
+        assert!(dag.in_degree(3).is_none());
+        assert!(dag.out_degree(3).is_none());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn remove_vertex_does_not_exist() {
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        dag.add_vertex(0);
+        assert_eq!(dag.remove_vertex(1).unwrap_err(), Error::VertexDoesNotExist);
 
+        // Test passed.
+    }
+
+    #[test]
+    fn roots_and_leaves() -> Result<(), Error> {
+        // Add five nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        dag.add_vertex(0);
+        dag.add_vertex(1);
+        dag.add_vertex(2);
+        dag.add_vertex(3);
+        dag.add_vertex(4);
+
+        // Add an edge (0, 2), (1, 2), and (2, 3).
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 2)?;
+        dag.add_edge(2, 3)?;
+
+        // Vertex 4 is isolated, so it is both a root and a leaf.
+        let mut exp_roots: BTreeSet<&usize> = BTreeSet::new();
+        exp_roots.insert(&0);
+        exp_roots.insert(&1);
+        exp_roots.insert(&4);
+        assert_eq!(dag.roots(), exp_roots);
+
+        let mut exp_leaves: BTreeSet<&usize> = BTreeSet::new();
+        exp_leaves.insert(&3);
+        exp_leaves.insert(&4);
+        assert_eq!(dag.leaves(), exp_leaves);
+
+        // Removing vertex 2 turns 0, and 1 into leaves, and 3 into a root.
+        dag.remove_vertex(2)?;
+        let mut exp_roots: BTreeSet<&usize> = BTreeSet::new();
+        exp_roots.insert(&0);
+        exp_roots.insert(&1);
+        exp_roots.insert(&3);
+        exp_roots.insert(&4);
+        assert_eq!(dag.roots(), exp_roots);
+        assert_eq!(dag.leaves(), exp_roots);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn ancestors() -> Result<(), Error> {
+        // Add five nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        dag.add_vertex(0);
+        dag.add_vertex(1);
+        dag.add_vertex(2);
+        dag.add_vertex(3);
+        dag.add_vertex(4);
+
+        // Add an edge (0, 1), (1, 3), (2, 3), and (3, 4).
+        dag.add_edge(0, 1)?;
+        dag.add_edge(1, 3)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(3, 4)?;
+
+        let mut exp_ancestors_4: BTreeSet<&usize> = BTreeSet::new();
+        exp_ancestors_4.insert(&0);
+        exp_ancestors_4.insert(&1);
+        exp_ancestors_4.insert(&2);
+        exp_ancestors_4.insert(&3);
+        assert_eq!(dag.ancestors(4).unwrap(), exp_ancestors_4);
+        assert!(dag.ancestors(0).unwrap().is_empty());
+
+        let bfs: Vec<&usize> = dag
+            .ancestors_iter(4, Traversal::BreadthFirst)
+            .unwrap()
+            .collect();
+        assert_eq!(bfs, vec![&3, &1, &2, &0]);
+
+        let dfs: Vec<&usize> = dag
+            .ancestors_iter(4, Traversal::DepthFirst)
+            .unwrap()
+            .collect();
+        assert_eq!(dfs, vec![&3, &1, &0, &2]);
+
+        assert!(dag.ancestors(5).is_none());
+        assert!(dag.ancestors_iter(5, Traversal::DepthFirst).is_none());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn descendants() -> Result<(), Error> {
+        // Add five nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        dag.add_vertex(0);
+        dag.add_vertex(1);
+        dag.add_vertex(2);
+        dag.add_vertex(3);
+        dag.add_vertex(4);
+
+        // Add an edge (0, 1), (0, 2), (1, 3), (2, 3), and (3, 4).
+        dag.add_edge(0, 1)?;
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 3)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(3, 4)?;
+
+        let mut exp_descendants_0: BTreeSet<&usize> = BTreeSet::new();
+        exp_descendants_0.insert(&1);
+        exp_descendants_0.insert(&2);
+        exp_descendants_0.insert(&3);
+        exp_descendants_0.insert(&4);
+        assert_eq!(dag.descendants(0).unwrap(), exp_descendants_0);
+        assert!(dag.descendants(4).unwrap().is_empty());
+
+        // The diamond below vertex 0 is only visited once.
+        let bfs: Vec<&usize> = dag
+            .descendants_iter(0, Traversal::BreadthFirst)
+            .unwrap()
+            .collect();
+        assert_eq!(bfs, vec![&1, &2, &3, &4]);
+
+        let dfs: Vec<&usize> = dag
+            .descendants_iter(0, Traversal::DepthFirst)
+            .unwrap()
+            .collect();
+        assert_eq!(dfs, vec![&1, &3, &4, &2]);
+
+        // The dag is left untouched.
+        assert_eq!(dag.vertices().len(), 5);
+
+        assert!(dag.descendants(5).is_none());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn has_path() -> Result<(), Error> {
+        // Add five nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        dag.add_vertex(0);
+        dag.add_vertex(1);
+        dag.add_vertex(2);
+        dag.add_vertex(3);
+        dag.add_vertex(4);
+
+        // Add an edge (0, 1), (1, 2), and (3, 2).
+        dag.add_edge(0, 1)?;
+        dag.add_edge(1, 2)?;
+        dag.add_edge(3, 2)?;
+
+        // There is a path from 0 to 2 through 1.
+        assert!(dag.has_path(0, 1)?);
+        assert!(dag.has_path(0, 2)?);
+        assert!(dag.has_path(3, 2)?);
+        // Paths are directed.
+        assert!(!dag.has_path(2, 0)?);
+        // Vertices 0, and 3 share a descendant, but neither reaches the other.
+        assert!(!dag.has_path(0, 3)?);
+        assert!(!dag.has_path(3, 0)?);
+        // Vertex 4 is isolated, but reaches itself.
+        assert!(!dag.has_path(4, 0)?);
+        assert!(dag.has_path(4, 4)?);
+
+        // If we attempt to check reachability on a node that does not exist,
+        // an error will be raised.
+        assert_eq!(dag.has_path(0, 5).unwrap_err(), Error::VertexDoesNotExist);
+        assert_eq!(dag.has_path(5, 0).unwrap_err(), Error::VertexDoesNotExist);
+        assert_eq!(dag.has_path(5, 5).unwrap_err(), Error::VertexDoesNotExist);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn add_edge_cycle_check() -> Result<(), Error> {
+        // Build a complete dag on 64 vertices, i.e. an edge (i, j) for every
+        // i < j. There are 2^62 paths from vertex 0 to vertex 63, so the cycle
+        // check must not enumerate paths.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..64 {
+            dag.add_vertex(i);
+        }
+        for i in 0..64 {
+            for j in (i + 1)..64 {
+                dag.add_edge(i, j)?;
+            }
+        }
+
+        // Re-adding an existing edge searches the whole dag below it.
+        dag.add_edge(0, 1)?;
+        assert_eq!(
+            dag.add_edge(63, 0).unwrap_err(),
+            EdgeError::Cycle(vec![0, 63])
+        );
+
+        // A self loop is a cycle.
+        assert_eq!(dag.add_edge(5, 5).unwrap_err(), EdgeError::Cycle(vec![5]));
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn incremental_topological_order() -> Result<(), Error> {
+        // Add six nodes; each is appended to the order.
+        let mut dag: IncrementalBTreeDAG<usize> = IncrementalBTreeDAG::new();
+        dag.add_vertex(5);
+        dag.add_vertex(4);
+        dag.add_vertex(3);
+        dag.add_vertex(2);
+        dag.add_vertex(1);
+        dag.add_vertex(0);
+        assert_eq!(dag.topological_order(), vec![&5, &4, &3, &2, &1, &0]);
+
+        // An edge which agrees with the order leaves it untouched.
+        dag.add_edge(5, 0)?;
+        assert_eq!(dag.topological_order(), vec![&5, &4, &3, &2, &1, &0]);
+
+        // Edges which disagree with the order reorder the affected region.
+        dag.add_edge(0, 1)?;
+        dag.add_edge(1, 2)?;
+        dag.add_edge(3, 2)?;
+        dag.add_edge(2, 4)?;
+        let order: Vec<usize> = dag.topological_order().into_iter().cloned().collect();
+        assert_eq!(order.len(), 6);
+        for (i, x) in order.iter().enumerate() {
+            for y in dag.connections(*x).unwrap() {
+                assert!(order[i..].contains(y));
+            }
+        }
+        assert!(dag.position(&0).unwrap() < dag.position(&4).unwrap());
+
+        // Cycles are still rejected, and leave the order untouched.
+        assert_eq!(
+            dag.add_edge(4, 5).unwrap_err(),
+            EdgeError::Cycle(vec![5, 0, 1, 2, 4])
+        );
+        assert_eq!(dag.add_edge(4, 4).unwrap_err(), EdgeError::Cycle(vec![4]));
+        assert_eq!(
+            dag.topological_order(),
+            order.iter().collect::<Vec<&usize>>()
+        );
+        assert_eq!(
+            dag.add_edge(4, 6).unwrap_err(),
+            EdgeError::VertexDoesNotExist
+        );
+
+        // Removed vertices leave the order.
+        dag.remove_vertex(2)?;
+        assert_eq!(dag.topological_order().len(), 5);
+        assert!(dag.position(&2).is_none());
+
+        // Equality compares the dags, not the orders they maintain.
+        let rebuilt: IncrementalBTreeDAG<usize> = dag.as_dag().clone().into();
+        assert_ne!(rebuilt.topological_order(), dag.topological_order());
+        assert_eq!(rebuilt, dag);
+        let mut forwards: IncrementalBTreeDAG<usize> = IncrementalBTreeDAG::new();
+        let mut backwards: IncrementalBTreeDAG<usize> = IncrementalBTreeDAG::new();
+        for i in 0..3 {
+            forwards.add_vertex(i);
+            backwards.add_vertex(2 - i);
+        }
+        assert_ne!(forwards.topological_order(), backwards.topological_order());
+        assert_eq!(forwards, backwards);
+        backwards.add_edge(0, 1)?;
+        assert_ne!(forwards, backwards);
+
+        // Pruned vertices leave the order, whichever the mode. Pruning 5 orphans
+        // 0, and then 1, while 4 keeps its parent 3.
+        let mut pruned = dag.clone();
+        pruned.add_edge(1, 4)?;
+        pruned.add_edge(3, 4)?;
+        let removed = pruned.prune_with(5, PruneMode::Orphans)?;
+        let mut exp_removed: BTreeSet<usize> = BTreeSet::new();
+        exp_removed.insert(5);
+        exp_removed.insert(0);
+        exp_removed.insert(1);
+        assert_eq!(removed, exp_removed);
+        assert_eq!(pruned.topological_order(), vec![&3, &4]);
+        assert!(pruned.position(&0).is_none());
+        assert_eq!(pruned.prune(3)?.len(), 2);
+        assert!(pruned.topological_order().is_empty());
+        assert_eq!(pruned.prune(3).unwrap_err(), Error::VertexDoesNotExist);
+
+        // Converting from a `BTreeDAG` starts from its topological sort.
+        let mut btree_dag: BTreeDAG<usize> = BTreeDAG::new();
+        btree_dag.add_vertex(0);
+        btree_dag.add_vertex(1);
+        btree_dag.add_edge(1, 0)?;
+        let dag: IncrementalBTreeDAG<usize> = btree_dag.clone().into();
+        assert_eq!(dag.topological_order(), vec![&1, &0]);
+        assert_eq!(dag.into_dag(), btree_dag);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn incremental_matches_btree_dag() -> Result<(), Error> {
+        // Insert the same edges, in a scrambled order, into both types and
+        // check they agree on which edges are accepted.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        let mut incremental: IncrementalBTreeDAG<usize> = IncrementalBTreeDAG::new();
+        for i in 0..32 {
+            dag.add_vertex(i);
+            incremental.add_vertex(i);
+        }
+        let mut seed: usize = 7;
+        for _ in 0..512 {
+            seed = (seed * 1103515245 + 12345) % (1 << 31);
+            let x = seed % 32;
+            let y = (seed / 32) % 32;
+            assert_eq!(
+                dag.add_edge(x, y).is_ok(),
+                incremental.add_edge(x, y).is_ok()
+            );
+        }
+        assert_eq!(&dag, incremental.as_dag());
+
+        // The maintained order is a valid topological order.
+        let order = incremental.topological_order();
+        for (i, x) in order.iter().enumerate() {
+            for y in incremental.connections(**x).unwrap() {
+                assert!(order[i..].contains(&y));
+            }
+        }
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn would_create_cycle() -> Result<(), Error> {
+        // Add four nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        dag.add_vertex(0);
+        dag.add_vertex(1);
+        dag.add_vertex(2);
+        dag.add_vertex(3);
+
+        // Add an edge (0, 1), and (1, 2).
+        dag.add_edge(0, 1)?;
+        dag.add_edge(1, 2)?;
+
+        assert!(dag.would_create_cycle(&2, &0)?);
+        assert!(dag.would_create_cycle(&1, &0)?);
+        assert!(dag.would_create_cycle(&3, &3)?);
+        assert!(!dag.would_create_cycle(&0, &2)?);
+        assert!(!dag.would_create_cycle(&2, &3)?);
+        assert!(!dag.would_create_cycle(&3, &0)?);
+
+        // The answer agrees with `add_edge`, and the dag is left untouched.
+        let before = dag.clone();
+        assert!(dag.add_edge(2, 0).is_err());
+        assert_eq!(dag, before);
+
+        assert_eq!(
+            dag.would_create_cycle(&0, &4).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+        assert_eq!(
+            dag.would_create_cycle(&4, &0).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+
+        // The incremental dag answers the same.
+        let incremental: IncrementalBTreeDAG<usize> = dag.into();
+        assert!(incremental.would_create_cycle(&2, &0)?);
+        assert!(incremental.would_create_cycle(&3, &3)?);
+        assert!(!incremental.would_create_cycle(&0, &2)?);
+        assert!(!incremental.would_create_cycle(&3, &0)?);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn transitive_reduction() -> Result<(), Error> {
+        // Build a complete dag on five vertices, i.e. an edge (i, j) for
+        // every i < j.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..5 {
+            dag.add_vertex(i);
+        }
+        for i in 0..5 {
+            for j in (i + 1)..5 {
+                dag.add_edge(i, j)?;
+            }
+        }
+
+        // Every edge but (i, i + 1) is a shortcut.
+        let redundant_edges = dag.redundant_edges();
+        assert_eq!(redundant_edges.len(), 6);
+        assert!(redundant_edges.contains(&(&0, &2)));
+        assert!(redundant_edges.contains(&(&0, &4)));
+        assert!(!redundant_edges.contains(&(&0, &1)));
+
+        // The reduction is the path 0 -> 1 -> 2 -> 3 -> 4.
+        let reduced = dag.transitive_reduction();
+        let mut exp_dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..5 {
+            exp_dag.add_vertex(i);
+        }
+        for i in 0..4 {
+            exp_dag.add_edge(i, i + 1)?;
+        }
+        assert_eq!(reduced, exp_dag);
+        assert!(reduced.redundant_edges().is_empty());
+
+        // Reachability is preserved.
+        for i in 0..5 {
+            for j in 0..5 {
+                assert_eq!(dag.has_path(i, j)?, reduced.has_path(i, j)?);
+            }
+        }
+
+        // Reducing in place returns the removed edges.
+        let removed = dag.reduce();
+        assert_eq!(removed.len(), 6);
+        assert!(removed.contains(&(1, 3)));
+        assert_eq!(dag, exp_dag);
+        assert!(dag.parents(3).unwrap().contains(&2));
+        assert!(!dag.parents(3).unwrap().contains(&1));
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn transitive_closure() -> Result<(), Error> {
+        // Add five nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        dag.add_vertex(0);
+        dag.add_vertex(1);
+        dag.add_vertex(2);
+        dag.add_vertex(3);
+        dag.add_vertex(4);
+
+        // Add an edge (0, 1), (1, 2), (0, 3), and (3, 2).
+        dag.add_edge(0, 1)?;
+        dag.add_edge(1, 2)?;
+        dag.add_edge(0, 3)?;
+        dag.add_edge(3, 2)?;
+
+        let closure = dag.transitive_closure();
+
+        let mut exp_connections_0: BTreeSet<usize> = BTreeSet::new();
+        exp_connections_0.insert(1);
+        exp_connections_0.insert(2);
+        exp_connections_0.insert(3);
+        assert_eq!(closure.connections(0).unwrap(), &exp_connections_0);
+
+        let mut exp_parents_2: BTreeSet<usize> = BTreeSet::new();
+        exp_parents_2.insert(0);
+        exp_parents_2.insert(1);
+        exp_parents_2.insert(3);
+        assert_eq!(closure.parents(2).unwrap(), &exp_parents_2);
+
+        // Adjacency in the closure is reachability in the dag.
+        for i in 0..5 {
+            for j in 0..5 {
+                assert_eq!(closure.adjacent(i, j)?, i != j && dag.has_path(i, j)?);
+            }
+        }
+
+        // The closure of the closure is itself, and it reduces back to the dag.
+        assert_eq!(closure.transitive_closure(), closure);
+        assert_eq!(closure.transitive_reduction(), dag);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn reachability_index() -> Result<(), Error> {
+        // Add 96 nodes, so the bitset spans more than one word per row, and
+        // insert pseudo random edges.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..96 {
+            dag.add_vertex(i);
+        }
+        let mut seed: usize = 11;
+        for _ in 0..256 {
+            seed = (seed * 1103515245 + 12345) % (1 << 31);
+            let x = seed % 96;
+            let y = (seed / 96) % 96;
+            let _ = dag.add_edge(x, y);
+        }
+
+        // Both labelings agree with a traversal of the dag.
+        let bitset = ReachabilityIndex::bitset(&dag);
+        let intervals = ReachabilityIndex::intervals(&dag);
+        assert_eq!(ReachabilityIndex::new(&dag), bitset);
+        assert_eq!(bitset.len(), 96);
+        for i in 0..96 {
+            for j in 0..96 {
+                let exp = dag.has_path(i, j)?;
+                assert_eq!(bitset.reaches(&i, &j)?, exp);
+                assert_eq!(intervals.reaches(&i, &j)?, exp);
+                assert_eq!(intervals.has_path(i, j)?, exp);
+            }
+        }
+
+        // If we attempt to check reachability on a node that does not exist,
+        // an error will be raised.
+        assert_eq!(
+            bitset.reaches(&0, &96).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+        assert_eq!(
+            intervals.reaches(&96, &0).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+
+        // An empty dag has an empty index.
+        let dag: BTreeDAG<usize> = BTreeDAG::new();
+        assert!(ReachabilityIndex::bitset(&dag).is_empty());
+        assert!(ReachabilityIndex::intervals(&dag).is_empty());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn critical_path() -> Result<(), Error> {
+        // Add six nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..6 {
+            dag.add_vertex(i);
+        }
+
+        // Add an edge (0, 1), (0, 2), (1, 3), (2, 3), (3, 4); vertex 5 is
+        // isolated.
+        dag.add_edge(0, 1)?;
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 3)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(3, 4)?;
+
+        // With unit costs, the longest path is the one with the most vertices,
+        // and ties are broken by the first path found.
+        let (path, cost) = dag.critical_path(|_| 1).unwrap();
+        assert_eq!(path, vec![&0, &1, &3, &4]);
+        assert_eq!(cost, 4);
+
+        // Making vertex 2 expensive moves the critical path through it.
+        let (path, cost) = dag.critical_path(|v| if *v == 2 { 10 } else { 1 }).unwrap();
+        assert_eq!(path, vec![&0, &2, &3, &4]);
+        assert_eq!(cost, 13);
+
+        // An expensive isolated vertex is a critical path by itself.
+        let (path, cost) = dag
+            .critical_path(|v| if *v == 5 { 100 } else { 1 })
+            .unwrap();
+        assert_eq!(path, vec![&5]);
+        assert_eq!(cost, 100);
+
+        // Edge costs are included.
+        let (path, cost) = dag
+            .critical_path_with_edges(|_| 1, |x, y| if (*x, *y) == (1, 3) { 7 } else { 0 })
+            .unwrap();
+        assert_eq!(path, vec![&0, &1, &3, &4]);
+        assert_eq!(cost, 11);
+
+        // An empty dag has no critical path.
+        let dag: BTreeDAG<usize> = BTreeDAG::new();
+        assert!(dag.critical_path(|_| 1).is_none());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn critical_path_negative_costs() -> Result<(), Error> {
+        // Add three nodes, and edges (0, 1), and (1, 2).
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..3 {
+            dag.add_vertex(i);
+        }
+        dag.add_edge(0, 1)?;
+        dag.add_edge(1, 2)?;
+
+        // A negative parent is left out of the path.
+        let cost_of = |v: &usize| [-5, 3, 0][*v];
+        let (path, cost) = dag.critical_path(cost_of).unwrap();
+        assert_eq!(path, vec![&1]);
+        assert_eq!(cost, 3);
+
+        // A negative vertex is kept if the path through it still pays off.
+        let cost_of = |v: &usize| [2, -1, 5][*v];
+        let (path, cost) = dag.critical_path(cost_of).unwrap();
+        assert_eq!(path, vec![&0, &1, &2]);
+        assert_eq!(cost, 6);
+
+        // A negative edge splits the path.
+        let (path, cost) = dag
+            .critical_path_with_edges(|_| 4, |x, _| if *x == 0 { -10 } else { 0 })
+            .unwrap();
+        assert_eq!(path, vec![&1, &2]);
+        assert_eq!(cost, 8);
+
+        // If every vertex is negative, the least negative is the path.
+        let cost_of = |v: &usize| [-3, -1, -2][*v];
+        let (path, cost) = dag.critical_path(cost_of).unwrap();
+        assert_eq!(path, vec![&1]);
+        assert_eq!(cost, -1);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn schedule() -> Result<(), Error> {
+        // Add six nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..6 {
+            dag.add_vertex(i);
+        }
+
+        // Add an edge (0, 1), (0, 2), (1, 3), (2, 3), and (3, 4); vertex 5 is
+        // isolated.
+        dag.add_edge(0, 1)?;
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 3)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(3, 4)?;
+
+        // Durations: 0 -> 2, 1 -> 4, 2 -> 1, 3 -> 3, 4 -> 2, 5 -> 5.
+        let durations: [usize; 6] = [2, 4, 1, 3, 2, 5];
+        let schedule = dag.schedule(|v| durations[*v]);
+
+        // The critical path is 0 -> 1 -> 3 -> 4.
+        assert_eq!(schedule.makespan(), 11);
+        let mut exp_critical: BTreeSet<&usize> = BTreeSet::new();
+        exp_critical.insert(&0);
+        exp_critical.insert(&1);
+        exp_critical.insert(&3);
+        exp_critical.insert(&4);
+        assert_eq!(schedule.critical_vertices(), exp_critical);
+
+        assert_eq!(
+            schedule.timing(&2),
+            Some(&Timing {
+                earliest_start: 2,
+                earliest_finish: 3,
+                latest_start: 5,
+                latest_finish: 6,
+                slack: 3,
+            })
+        );
+        assert_eq!(
+            schedule.timing(&4),
+            Some(&Timing {
+                earliest_start: 9,
+                earliest_finish: 11,
+                latest_start: 9,
+                latest_finish: 11,
+                slack: 0,
+            })
+        );
+
+        // The isolated vertex may start at any time before the makespan.
+        assert_eq!(schedule.timing(&5).unwrap().slack, 6);
+
+        assert!(schedule.timing(&6).is_none());
+        assert_eq!(schedule.timings().len(), 6);
+
+        // The makespan agrees with the cost of the critical path.
+        let (_, cost) = dag.critical_path(|v| durations[*v]).unwrap();
+        assert_eq!(schedule.makespan(), cost);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn shortest_path() -> Result<(), Error> {
+        // Add six nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..6 {
+            dag.add_vertex(i);
+        }
+
+        // Add an edge (0, 1), (1, 2), (2, 3), (0, 4), (4, 3), and (3, 5).
+        dag.add_edge(0, 1)?;
+        dag.add_edge(1, 2)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(0, 4)?;
+        dag.add_edge(4, 3)?;
+        dag.add_edge(3, 5)?;
+
+        // The route through 4 has the fewest hops.
+        assert_eq!(dag.shortest_path(0, 5)?, Some(vec![0, 4, 3, 5]));
+        assert_eq!(dag.shortest_path(1, 5)?, Some(vec![1, 2, 3, 5]));
+
+        // A shortcut edge is preferred once added.
+        dag.add_edge(0, 3)?;
+        assert_eq!(dag.shortest_path(0, 5)?, Some(vec![0, 3, 5]));
+
+        // Every vertex reaches itself, and paths are directed.
+        assert_eq!(dag.shortest_path(2, 2)?, Some(vec![2]));
+        assert_eq!(dag.shortest_path(5, 0)?, None);
+        assert_eq!(dag.shortest_path(4, 1)?, None);
+
+        // If we attempt to find a path to or from a node that does not exist,
+        // an error will be raised.
+        assert_eq!(
+            dag.shortest_path(0, 6).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+        assert_eq!(
+            dag.shortest_path(6, 0).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn all_paths() -> Result<(), Error> {
+        // Add six nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..6 {
+            dag.add_vertex(i);
+        }
+
+        // Add an edge (0, 1), (0, 2), (1, 3), (2, 3), (0, 3), (3, 4), and
+        // (1, 5); vertex 5 is a dead end.
+        dag.add_edge(0, 1)?;
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 3)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(0, 3)?;
+        dag.add_edge(3, 4)?;
+        dag.add_edge(1, 5)?;
+
+        let paths: Vec<Vec<&usize>> = dag.paths(0, 4)?.collect();
+        assert_eq!(
+            paths,
+            vec![vec![&0, &1, &3, &4], vec![&0, &2, &3, &4], vec![&0, &3, &4],]
+        );
+        assert_eq!(dag.count_paths(0, 4)?, Some(3));
+
+        // Every vertex reaches itself by a single path.
+        assert_eq!(
+            dag.paths(2, 2)?.collect::<Vec<Vec<&usize>>>(),
+            vec![vec![&2]]
+        );
+        assert_eq!(dag.count_paths(2, 2)?, Some(1));
+
+        // There are no paths against the edges.
+        assert_eq!(dag.paths(4, 0)?.count(), 0);
+        assert_eq!(dag.count_paths(4, 0)?, Some(0));
+        assert_eq!(dag.count_paths(5, 4)?, Some(0));
+
+        // If we attempt to enumerate paths on a node that does not exist,
+        // an error will be raised.
+        assert!(dag.paths(0, 6).is_err());
+        assert_eq!(
+            dag.count_paths(6, 0).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn count_paths_overflow() -> Result<(), Error> {
+        // A chain of 66 diamonds doubles the number of paths 66 times.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        dag.add_vertex(0);
+        for i in 0..66 {
+            let (top, left, right, bottom) = (3 * i, 3 * i + 1, 3 * i + 2, 3 * i + 3);
+            dag.add_vertex(left);
+            dag.add_vertex(right);
+            dag.add_vertex(bottom);
+            dag.add_edge(top, left)?;
+            dag.add_edge(top, right)?;
+            dag.add_edge(left, bottom)?;
+            dag.add_edge(right, bottom)?;
+        }
+
+        // 2^63 paths still fit, but 2^64 do not.
+        assert_eq!(dag.count_paths(0, 3 * 63)?, Some(1 << 63));
+        assert_eq!(dag.count_paths(0, 3 * 64)?, None);
+        assert_eq!(dag.count_paths(0, 3 * 66)?, None);
+
+        // The overflowing count of the bottom vertex does not affect
+        // counts which do not depend on it.
+        assert_eq!(dag.count_paths(3 * 65, 3 * 66)?, Some(2));
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn common_ancestors() -> Result<(), Error> {
+        // Add seven nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..7 {
+            dag.add_vertex(i);
+        }
+
+        // Model a criss-cross merge: 1 and 2 branch from 0, and both 3 and 4
+        // merge 1 and 2; 5 extends 3, and 6 extends 4.
+        dag.add_edge(0, 1)?;
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 3)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(1, 4)?;
+        dag.add_edge(2, 4)?;
+        dag.add_edge(3, 5)?;
+        dag.add_edge(4, 6)?;
+
+        let mut exp_common: BTreeSet<&usize> = BTreeSet::new();
+        exp_common.insert(&0);
+        exp_common.insert(&1);
+        exp_common.insert(&2);
+        assert_eq!(dag.common_ancestors(5, 6)?, exp_common);
+
+        // Both 1 and 2 are merge bases.
+        let mut exp_lowest: BTreeSet<&usize> = BTreeSet::new();
+        exp_lowest.insert(&1);
+        exp_lowest.insert(&2);
+        assert_eq!(dag.lowest_common_ancestors(5, 6)?, exp_lowest);
+
+        // A vertex is its own ancestor.
+        let mut exp_lowest: BTreeSet<&usize> = BTreeSet::new();
+        exp_lowest.insert(&3);
+        assert_eq!(dag.lowest_common_ancestors(3, 5)?, exp_lowest);
+
+        // The multi-vertex variant intersects all of them.
+        let mut exp_lowest: BTreeSet<&usize> = BTreeSet::new();
+        exp_lowest.insert(&1);
+        assert_eq!(dag.lowest_common_ancestors_of(vec![5, 6, 1])?, exp_lowest);
+        assert!(dag.common_ancestors_of(Vec::new())?.is_empty());
+
+        // Disconnected vertices share no ancestors.
+        dag.add_vertex(7);
+        assert!(dag.common_ancestors(5, 7)?.is_empty());
+        assert!(dag.lowest_common_ancestors(5, 7)?.is_empty());
+
+        // If we attempt to find ancestors of a node that does not exist,
+        // an error will be raised.
+        assert_eq!(
+            dag.common_ancestors(5, 8).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+        assert_eq!(
+            dag.lowest_common_ancestors_of(vec![8]).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn dominators() -> Result<(), Error> {
+        // Add eight nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..8 {
+            dag.add_vertex(i);
+        }
+
+        // Add an edge (0, 1), (1, 2), (1, 3), (2, 4), (3, 4), (4, 5), (0, 5),
+        // and (6, 4); vertex 7 is isolated.
+        dag.add_edge(0, 1)?;
+        dag.add_edge(1, 2)?;
+        dag.add_edge(1, 3)?;
+        dag.add_edge(2, 4)?;
+        dag.add_edge(3, 4)?;
+        dag.add_edge(4, 5)?;
+        dag.add_edge(0, 5)?;
+        dag.add_edge(6, 4)?;
+
+        let tree = dag.dominator_tree(0).unwrap();
+        assert_eq!(tree.root(), &0);
+        assert_eq!(tree.immediate_dominator(&0), None);
+        assert_eq!(tree.immediate_dominator(&1), Some(&0));
+        assert_eq!(tree.immediate_dominator(&2), Some(&1));
+        assert_eq!(tree.immediate_dominator(&4), Some(&1));
+        // The edge (0, 5) bypasses 1, and 4.
+        assert_eq!(tree.immediate_dominator(&5), Some(&0));
+        // Vertex 6 is not reachable from the root, so its edge into 4 is
+        // ignored.
+        assert_eq!(tree.immediate_dominator(&6), None);
+        assert!(!tree.vertices().contains(&6));
+
+        assert!(tree.dominates(&1, &4));
+        assert!(tree.dominates(&4, &4));
+        assert!(tree.dominates(&0, &5));
+        assert!(!tree.dominates(&2, &4));
+        assert!(!tree.dominates(&1, &5));
+        assert!(!tree.dominates(&0, &7));
+        assert_eq!(tree.dominators(&4), Some(vec![&4, &1, &0]));
+        assert_eq!(tree.dominators(&7), None);
+
+        // Every path from 1, 2, 3, 4, and 6 to the sink passes through 4.
+        let tree = dag.post_dominator_tree(5).unwrap();
+        assert_eq!(tree.immediate_dominator(&2), Some(&4));
+        assert_eq!(tree.immediate_dominator(&1), Some(&4));
+        assert_eq!(tree.immediate_dominator(&6), Some(&4));
+        assert_eq!(tree.immediate_dominator(&4), Some(&5));
+        assert_eq!(tree.immediate_dominator(&0), Some(&5));
+        assert!(tree.dominates(&4, &1));
+        assert!(!tree.dominates(&4, &0));
+
+        assert!(dag.dominator_tree(8).is_none());
+        assert!(dag.post_dominator_tree(8).is_none());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn impact_analysis() -> Result<(), Error> {
+        // Add seven nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..7 {
+            dag.add_vertex(i);
+        }
+
+        // Add an edge (0, 2), (1, 2), (2, 3), (3, 4), (1, 5), (6, 3).
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 2)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(3, 4)?;
+        dag.add_edge(1, 5)?;
+        dag.add_edge(6, 3)?;
+
+        // Edges into the affected region from outside of it (e.g. (1, 2), and
+        // (6, 3)) do not hold it back.
+        assert_eq!(dag.affected(vec![0])?, vec![&0, &2, &3, &4]);
+        assert_eq!(dag.affected(vec![1])?, vec![&1, &2, &3, &4, &5]);
+        assert_eq!(dag.affected(vec![4, 6])?, vec![&6, &3, &4]);
+        assert!(dag.affected(Vec::new())?.is_empty());
+
+        // The affected vertices respect the order of the dag.
+        let affected = dag.affected(vec![0, 1, 6])?;
+        assert_eq!(affected.len(), 7);
+        for (i, x) in affected.iter().enumerate() {
+            for y in dag.connections(**x).unwrap() {
+                assert!(affected[i..].contains(&y));
+            }
+        }
+
+        // Only the interesting vertices are kept.
+        assert_eq!(dag.affected_within(vec![1], vec![4, 5, 0])?, vec![&4, &5]);
+        assert!(dag.affected_within(vec![5], vec![4])?.is_empty());
+
+        // The dag is left untouched.
+        assert_eq!(dag.vertices().len(), 7);
+
+        // If we attempt to analyse a node that does not exist, an error will
+        // be raised.
+        assert_eq!(
+            dag.affected(vec![7]).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+        assert_eq!(
+            dag.affected_within(vec![0], vec![7]).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn prune_diamond() -> Result<(), Error> {
+        // Add six nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..6 {
+            dag.add_vertex(i);
+        }
+
+        // Add a diamond (0, 1), (0, 2), (1, 3), (2, 3), a tail (3, 4), and a
+        // second parent of 2 and 4: (5, 2), and (5, 4).
+        dag.add_edge(0, 1)?;
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 3)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(3, 4)?;
+        dag.add_edge(5, 2)?;
+        dag.add_edge(5, 4)?;
+        let before = dag.clone();
+
+        // A full cascade visits the bottom of the diamond once, and removes
+        // children shared with surviving vertices.
+        let removed = dag.prune(0)?;
+        let mut exp_removed: BTreeSet<usize> = BTreeSet::new();
+        exp_removed.insert(0);
+        exp_removed.insert(1);
+        exp_removed.insert(2);
+        exp_removed.insert(3);
+        exp_removed.insert(4);
+        assert_eq!(removed, exp_removed);
+        let mut exp_vertices: BTreeSet<&usize> = BTreeSet::new();
+        exp_vertices.insert(&5);
+        assert_eq!(dag.vertices(), exp_vertices);
+        assert!(dag.connections(5).unwrap().is_empty());
+
+        // Orphans only: 2 survives through 5, and so do 3, and 4.
+        let mut dag = before.clone();
+        let removed = dag.prune_with(0, PruneMode::Orphans)?;
+        let mut exp_removed: BTreeSet<usize> = BTreeSet::new();
+        exp_removed.insert(0);
+        exp_removed.insert(1);
+        assert_eq!(removed, exp_removed);
+        assert_eq!(dag.vertices().len(), 4);
+        assert!(dag.parents(3).unwrap().contains(&2));
+        assert!(!dag.parents(3).unwrap().contains(&1));
+
+        // Pruning 5 as well orphans the rest.
+        let removed = dag.prune_with(5, PruneMode::Orphans)?;
+        assert_eq!(removed.len(), 4);
+        assert!(dag.vertices().is_empty());
+
+        // If we attempt to prune a node that does not exist, an error will be
+        // raised, and nothing is removed.
+        let mut dag = before;
+        assert_eq!(dag.prune(6).unwrap_err(), Error::VertexDoesNotExist);
+        assert_eq!(
+            dag.prune_with(6, PruneMode::Orphans).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+        assert_eq!(dag.vertices().len(), 6);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn preview_removal() -> Result<(), Error> {
+        // Add six nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..6 {
+            dag.add_vertex(i);
+        }
+
+        // Add a diamond (0, 1), (0, 2), (1, 3), (2, 3), a tail (3, 4), and a
+        // second parent of 2: (5, 2).
+        dag.add_edge(0, 1)?;
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 3)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(3, 4)?;
+        dag.add_edge(5, 2)?;
+        let before = dag.clone();
+
+        // Removing 2 removes the edges into, and out of it.
+        let removal = dag.remove_vertex_preview(2)?;
+        let mut exp_vertices: BTreeSet<&usize> = BTreeSet::new();
+        exp_vertices.insert(&2);
+        assert_eq!(removal.vertices, exp_vertices);
+        let mut exp_edges: BTreeSet<(&usize, &usize)> = BTreeSet::new();
+        exp_edges.insert((&0, &2));
+        exp_edges.insert((&5, &2));
+        exp_edges.insert((&2, &3));
+        assert_eq!(removal.edges, exp_edges);
+
+        // Pruning 0 removes all but 5, and edges between removed vertices
+        // are only listed once.
+        let removal = dag.prune_preview(0, PruneMode::Cascade)?;
+        assert_eq!(removal.vertices.len(), 5);
+        assert!(!removal.vertices.contains(&5));
+        assert_eq!(removal.edges.len(), 6);
+
+        // Only 0, and 1 are orphaned.
+        let removal = dag.prune_preview(0, PruneMode::Orphans)?;
+        let mut exp_vertices: BTreeSet<&usize> = BTreeSet::new();
+        exp_vertices.insert(&0);
+        exp_vertices.insert(&1);
+        assert_eq!(removal.vertices, exp_vertices);
+        let mut exp_edges: BTreeSet<(&usize, &usize)> = BTreeSet::new();
+        exp_edges.insert((&0, &1));
+        exp_edges.insert((&0, &2));
+        exp_edges.insert((&1, &3));
+        assert_eq!(removal.edges, exp_edges);
+
+        // The dag is left untouched.
+        assert_eq!(dag, before);
+
+        // Each preview agrees with what is actually removed.
+        for mode in &[PruneMode::Cascade, PruneMode::Orphans] {
+            let removal = before.prune_preview(0, *mode)?;
+            let exp_vertices: BTreeSet<usize> = removal.vertices.into_iter().cloned().collect();
+            let mut dag = before.clone();
+            assert_eq!(dag.prune_with(0, *mode)?, exp_vertices);
+            for (x, y) in removal.edges {
+                assert!(!dag.adjacent(*x, *y).unwrap_or(false));
+            }
+        }
+
+        // If we attempt to preview a node that does not exist, an error will
+        // be raised.
+        assert_eq!(
+            dag.remove_vertex_preview(6).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+        assert_eq!(
+            dag.prune_preview(6, PruneMode::Cascade).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn remove_vertex_bypass() -> Result<(), Error> {
+        // Add six nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..6 {
+            dag.add_vertex(i);
+        }
+
+        // Add edges (0, 2), (1, 2), (2, 3), (2, 4), (3, 5), and a shortcut
+        // (0, 3) which a bypass of 2 also needs.
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 2)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(2, 4)?;
+        dag.add_edge(3, 5)?;
+        dag.add_edge(0, 3)?;
+
+        // Every parent of 2 is connected to every child of 2, and the edges
+        // which already existed are not reported.
+        let added = dag.remove_vertex_bypass(2)?;
+        let mut exp_added: BTreeSet<(usize, usize)> = BTreeSet::new();
+        exp_added.insert((0, 4));
+        exp_added.insert((1, 3));
+        exp_added.insert((1, 4));
+        assert_eq!(added, exp_added);
+        assert!(!dag.vertices().contains(&2));
+        for (x, y) in &[(0, 3), (0, 4), (1, 3), (1, 4), (3, 5)] {
+            assert!(dag.adjacent(*x, *y)?);
+        }
+        let mut exp_parents: BTreeSet<usize> = BTreeSet::new();
+        exp_parents.insert(0);
+        exp_parents.insert(1);
+        assert_eq!(dag.parents(4), Some(&exp_parents));
+        assert!(dag.has_path(1, 5)?);
+
+        // Roots, and leaves are simply removed.
+        assert!(dag.remove_vertex_bypass(0)?.is_empty());
+        assert!(dag.remove_vertex_bypass(5)?.is_empty());
+        assert!(dag.connections(3).unwrap().is_empty());
+
+        // If we attempt to bypass a node that does not exist, an error will
+        // be raised.
+        assert_eq!(
+            dag.remove_vertex_bypass(2).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+
+        // The incremental dag keeps a valid order.
+        let mut dag: IncrementalBTreeDAG<usize> = IncrementalBTreeDAG::new();
+        for i in 0..3 {
+            dag.add_vertex(i);
+        }
+        dag.add_edge(0, 1)?;
+        dag.add_edge(1, 2)?;
+        dag.remove_vertex_bypass(1)?;
+        assert_eq!(dag.topological_order(), vec![&0, &2]);
+        assert_eq!(dag.as_dag().topological_sort(), dag.topological_order());
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn split_edge() -> Result<(), Error> {
+        // Add four nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..4 {
+            dag.add_vertex(i);
+        }
+
+        // Add edges (0, 1), (1, 2), and (2, 3).
+        dag.add_edge(0, 1)?;
+        dag.add_edge(1, 2)?;
+        dag.add_edge(2, 3)?;
+
+        // Split (1, 2) with 4.
+        dag.split_edge(1, 2, 4)?;
+        assert!(!dag.adjacent(1, 2)?);
+        assert!(dag.adjacent(1, 4)?);
+        assert!(dag.adjacent(4, 2)?);
+        let mut exp_parents: BTreeSet<usize> = BTreeSet::new();
+        exp_parents.insert(4);
+        assert_eq!(dag.parents(2), Some(&exp_parents));
+        assert_eq!(dag.topological_sort(), vec![&0, &1, &4, &2, &3]);
+
+        // On error, the dag is left untouched.
+        let before = dag.clone();
+        assert_eq!(
+            dag.split_edge(1, 2, 5).unwrap_err(),
+            Error::EdgeDoesNotExist
+        );
+        assert_eq!(dag.split_edge(2, 3, 0).unwrap_err(), Error::VertexExists);
+        assert_eq!(
+            dag.split_edge(5, 3, 6).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+        assert_eq!(
+            dag.split_edge(2, 5, 6).unwrap_err(),
+            Error::VertexDoesNotExist
+        );
+        assert_eq!(dag, before);
+
+        // The incremental dag moves the new vertex ahead of y.
+        let mut dag: IncrementalBTreeDAG<usize> = IncrementalBTreeDAG::new();
+        for i in 0..4 {
+            dag.add_vertex(i);
+        }
+        dag.add_edge(0, 1)?;
+        dag.add_edge(1, 2)?;
+        dag.add_edge(2, 3)?;
+        dag.split_edge(1, 2, 4)?;
+        assert_eq!(dag.topological_order(), vec![&0, &1, &4, &2, &3]);
+        assert!(dag.as_dag().adjacent(4, 2)?);
+        assert_eq!(
+            dag.split_edge(1, 2, 5).unwrap_err(),
+            Error::EdgeDoesNotExist
+        );
+        assert_eq!(dag.split_edge(1, 4, 3).unwrap_err(), Error::VertexExists);
+        assert_eq!(dag.vertices().len(), 5);
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[test]
+    fn merge_vertices() -> Result<(), EdgeError<usize>> {
+        // Add six nodes.
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..6 {
+            dag.add_vertex(i);
+        }
+
+        // Add edges (0, 1), (0, 2), (1, 3), (2, 4), (3, 5), and (1, 2).
+        dag.add_edge(0, 1)?;
+        dag.add_edge(0, 2)?;
+        dag.add_edge(1, 3)?;
+        dag.add_edge(2, 4)?;
+        dag.add_edge(3, 5)?;
+        dag.add_edge(1, 2)?;
+        let before = dag.clone();
+
+        // Merging 1, and 2 contracts the edge (1, 2).
+        dag.merge_vertices(1, 2, 1)?;
+        let mut exp_vertices: BTreeSet<&usize> = BTreeSet::new();
+        for i in &[0, 1, 3, 4, 5] {
+            exp_vertices.insert(i);
+        }
+        assert_eq!(dag.vertices(), exp_vertices);
+        let mut exp_children: BTreeSet<usize> = BTreeSet::new();
+        exp_children.insert(3);
+        exp_children.insert(4);
+        assert_eq!(dag.connections(1), Some(&exp_children));
+        let mut exp_parents: BTreeSet<usize> = BTreeSet::new();
+        exp_parents.insert(0);
+        assert_eq!(dag.parents(1), Some(&exp_parents));
+        assert!(dag.parents(4).unwrap().contains(&1));
+
+        // Merging into a new vertex.
+        dag.merge_vertices(4, 5, 6)?;
+        assert!(!dag.vertices().contains(&4));
+        assert!(dag.connections(1).unwrap().contains(&6));
+        assert!(dag.connections(3).unwrap().contains(&6));
+
+        // Merging 0, and 3 would turn the path between them into a cycle,
+        // in either order.
+        let mut dag = before;
+        assert_eq!(
+            dag.merge_vertices(0, 3, 0).unwrap_err(),
+            EdgeError::Cycle(vec![0, 1, 3])
+        );
+        assert_eq!(
+            dag.merge_vertices(3, 0, 7).unwrap_err(),
+            EdgeError::Cycle(vec![0, 1, 3])
+        );
+        // The edge (0, 2) is not the only path from 0 to 2.
+        assert_eq!(
+            dag.merge_vertices(0, 2, 2).unwrap_err(),
+            EdgeError::Cycle(vec![0, 1, 2])
+        );
+        assert_eq!(
+            dag.merge_vertices(0, 6, 0).unwrap_err(),
+            EdgeError::VertexDoesNotExist
+        );
+        assert_eq!(
+            dag.merge_vertices(4, 5, 0).unwrap_err(),
+            EdgeError::VertexExists
+        );
+        assert_eq!(
+            Error::from(EdgeError::<usize>::VertexExists),
+            Error::VertexExists
+        );
+        assert_eq!(dag.vertices().len(), 6);
+
+        // The incremental dag keeps a valid order.
+        let mut dag: IncrementalBTreeDAG<usize> = IncrementalBTreeDAG::new();
+        for i in 0..5 {
+            dag.add_vertex(i);
+        }
+        dag.add_edge(0, 1)?;
+        dag.add_edge(2, 3)?;
+        dag.add_edge(3, 4)?;
+        dag.merge_vertices(1, 2, 5)?;
+        assert_eq!(dag.topological_order(), vec![&0, &5, &3, &4]);
+        assert_eq!(
+            dag.merge_vertices(0, 4, 0).unwrap_err(),
+            EdgeError::Cycle(vec![0, 5, 3, 4])
+        );
+
+        // Test passed.
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use alloc::string::String;
+
+        // Add edges (0, 1), (0, 2), and (1, 2).
+        let mut dag: BTreeDAG<usize> = BTreeDAG::new();
+        for i in 0..3 {
+            dag.add_vertex(i);
+        }
+        dag.add_edge(0, 1).unwrap();
+        dag.add_edge(0, 2).unwrap();
+        dag.add_edge(1, 2).unwrap();
+
+        // Only the adjacency list is written, and the reverse adjacency list
+        // is rebuilt.
+        let json: String = serde_json::to_string(&dag).unwrap();
+        assert_eq!(json, r#"{"vertices":{"0":[1,2],"1":[2],"2":[]}}"#);
+        let de: BTreeDAG<usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(de, dag);
+        let mut exp_parents: BTreeSet<usize> = BTreeSet::new();
+        exp_parents.insert(0);
+        exp_parents.insert(1);
+        assert_eq!(de.parents(2), Some(&exp_parents));
+
+        // A given reverse adjacency list is ignored.
+        let de: BTreeDAG<usize> =
+            serde_json::from_str(r#"{"vertices":{"0":[1],"1":[]},"parents":{}}"#).unwrap();
+        let mut exp_roots: BTreeSet<&usize> = BTreeSet::new();
+        exp_roots.insert(&0);
+        assert_eq!(de.roots(), exp_roots);
+
+        // Edges to missing vertices, and cycles are rejected.
+        assert!(serde_json::from_str::<BTreeDAG<usize>>(r#"{"vertices":{"0":[1]}}"#).is_err());
+        assert!(
+            serde_json::from_str::<BTreeDAG<usize>>(r#"{"vertices":{"0":[1],"1":[0]}}"#).is_err()
+        );
+        assert!(serde_json::from_str::<BTreeDAG<usize>>(r#"{"vertices":{"0":[0]}}"#).is_err());
+
+        // The incremental dag only writes the dag, and rebuilds its order.
+        let mut dag: IncrementalBTreeDAG<usize> = IncrementalBTreeDAG::new();
+        for i in 0..3 {
+            dag.add_vertex(i);
+        }
+        dag.add_edge(2, 1).unwrap();
+        dag.add_edge(1, 0).unwrap();
+        let json: String = serde_json::to_string(&dag).unwrap();
+        assert_eq!(json, r#"{"dag":{"vertices":{"0":[],"1":[0],"2":[1]}}}"#);
+        let de: IncrementalBTreeDAG<usize> = serde_json::from_str(&json).unwrap();
+        assert_eq!(de, dag);
+        assert_eq!(de.topological_order(), vec![&2, &1, &0]);
+        assert_eq!(de.position(&0), Some(2));
+        assert!(serde_json::from_str::<IncrementalBTreeDAG<usize>>(
+            r#"{"dag":{"vertices":{"0":[1],"1":[0]}}}"#
+        )
+        .is_err());
+    }
 }
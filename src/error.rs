@@ -0,0 +1,54 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "fmt")]
+use core::fmt::{Debug, Display, Formatter, Result};
+
+use crate::Error;
+
+#[cfg(feature = "fmt")]
+static VERTEX_DOES_NOT_EXIST_ERROR: &str = "BForest Error: Vertex does not exist";
+#[cfg(feature = "fmt")]
+static VERTEX_EXISTS_ERROR: &str = "BForest Error: Vertex already exists";
+#[cfg(feature = "fmt")]
+static CYCLE_ERROR: &str = "BForest Error: Edge would create a cycle";
+
+/// `EdgeError` is returned by operations which add edges to a dag, or merge its
+/// vertices. Unlike `Error`, a rejected operation carries the path which it would
+/// have closed into a cycle.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum EdgeError<T> {
+    VertexDoesNotExist,
+    VertexExists,
+    /// Adding the edge (x, y) would close a cycle, since there already is a
+    /// path from y to x. The path is listed from y to x, both inclusive, so a
+    /// self loop (x, x) yields the path `[x]`. Merging two vertices would close
+    /// a cycle if there is a path from one to the other, other than an edge
+    /// between them; that path is listed instead.
+    Cycle(Vec<T>),
+}
+
+#[cfg(feature = "fmt")]
+impl<T> Display for EdgeError<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            EdgeError::VertexDoesNotExist => write!(f, "{}", VERTEX_DOES_NOT_EXIST_ERROR),
+            EdgeError::VertexExists => write!(f, "{}", VERTEX_EXISTS_ERROR),
+            EdgeError::Cycle(path) => write!(f, "{}: {:?}", CYCLE_ERROR, path),
+        }
+    }
+}
+
+/// A cycle is reported as `Error::EdgeExists`, as it was before `EdgeError`
+/// was introduced.
+impl<T> From<EdgeError<T>> for Error {
+    fn from(e: EdgeError<T>) -> Error {
+        match e {
+            EdgeError::VertexDoesNotExist => Error::VertexDoesNotExist,
+            EdgeError::VertexExists => Error::VertexExists,
+            EdgeError::Cycle(_) => Error::EdgeExists,
+        }
+    }
+}
@@ -6,3 +6,5 @@ pub type Error = btree_error::Error;
 
 mod dag;
 pub use dag::*;
+mod error;
+pub use error::*;